@@ -5,5 +5,111 @@ pub enum CurveError {
     #[msg("failed to do math")]
     Overflow,
     #[msg("slippage limit exceeded")]
-    Slippage
+    Slippage,
+    #[msg("decimals out of range")]
+    InvalidDecimals,
+    #[msg("trading is paused")]
+    Paused,
+    #[msg("unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("fees exceed 100%")]
+    InvalidFee,
+    #[msg("treasury split is invalid")]
+    InvalidTreasurySplit,
+    #[msg("treasury recipients do not match the configured split")]
+    TreasuryRecipientMismatch,
+    #[msg("traders cannot refer themselves")]
+    SelfReferral,
+    #[msg("creator id is too long")]
+    CreatorIdTooLong,
+    #[msg("creator already has a pool")]
+    PoolExists,
+    #[msg("signer is not the creator's linked wallet")]
+    NotCreator,
+    #[msg("pool is not the creator's current pool")]
+    NotCreatorPool,
+    #[msg("creator allocation can only be reserved at launch")]
+    NotAtLaunch,
+    #[msg("creator allocation is too large")]
+    AllocationTooLarge,
+    #[msg("vested allocations take the vesting accounts, liquid ones the creator's token account")]
+    AllocationAccountsMismatch,
+    #[msg("vesting duration must be positive")]
+    InvalidVestingDuration,
+    #[msg("vesting cliff must fall within the vesting period")]
+    InvalidVestingCliff,
+    #[msg("vesting schedule was already revoked")]
+    VestingRevoked,
+    #[msg("trading has not started")]
+    TradingNotStarted,
+    #[msg("launch buy limit exceeded")]
+    LaunchLimitExceeded,
+    #[msg("launch settings can only change before the first trade")]
+    AlreadyTrading,
+    #[msg("twap window must be positive")]
+    InvalidTwapWindow,
+    #[msg("twap window reaches past the oldest observation")]
+    TwapWindowTooLong,
+    #[msg("basket accounts do not match the legs")]
+    BasketAccountsMismatch,
+    #[msg("cannot swap a pool into itself")]
+    SwapSamePool,
+    #[msg("order trigger price not reached")]
+    TriggerNotReached,
+    #[msg("sell orders need a token escrow")]
+    MissingOrderEscrow,
+    #[msg("dca interval must be positive")]
+    InvalidDcaInterval,
+    #[msg("dca interval has not elapsed")]
+    DcaNotDue,
+    #[msg("price is above the dca maximum")]
+    DcaPriceTooHigh,
+    #[msg("budget does not buy any tokens")]
+    NothingToBuy,
+    #[msg("unstake amount exceeds staked balance")]
+    InsufficientStake,
+    #[msg("holder tiers must be ascending and within the maximum count")]
+    InvalidHolderTiers,
+    #[msg("tip memo too long")]
+    MemoTooLong,
+    #[msg("token tips need a mint and token accounts")]
+    MissingTipAccounts,
+    #[msg("quote token pools need the quote mint and token accounts")]
+    MissingQuoteAccounts,
+    #[msg("not supported on quote token pools")]
+    QuotePoolUnsupported,
+    #[msg("vault expiry period cannot be negative")]
+    InvalidVaultExpiry,
+    #[msg("vault expiry is disabled")]
+    VaultExpiryDisabled,
+    #[msg("creator vault has not expired")]
+    VaultNotExpired,
+    #[msg("only the linked creator or the program admin can close a pool")]
+    NotPoolCloser,
+    #[msg("pool still has supply")]
+    PoolNotEmpty,
+    #[msg("account already has the current layout")]
+    AlreadyMigrated,
+    #[msg("account does not match the legacy layout")]
+    InvalidLegacyAccount,
+    #[msg("pool reserve doesn't cover selling its whole supply")]
+    UnderfundedReserve,
+    #[msg("signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+    #[msg("withdrawal exceeds the treasury balance")]
+    InsufficientTreasury,
+    #[msg("self-funded vesting cannot be revoked")]
+    VestingNotRevocable,
+    #[msg("staked tokens are still locked")]
+    StakeLocked,
+    #[msg("missing accounts for the vault reclaim")]
+    MissingReclaimAccounts,
+    #[msg("stakers still have tokens or rewards in the pool")]
+    PoolHasStakers,
+    #[msg("buy orders don't take a token escrow")]
+    UnexpectedOrderEscrow,
+    #[msg("sell orders need the owner's token account")]
+    MissingOwnerAta
 }
\ No newline at end of file
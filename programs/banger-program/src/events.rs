@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CreatorTipped {
+    pub creator_id: String,
+    pub tipper: Pubkey,
+    pub mint: Option<Pubkey>, // None for SOL tips
+    pub amount: u64,
+    pub memo: Option<String>
+}
+
+#[event]
+pub struct PoolClosed {
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub creator_id: String,
+    pub closed_by: Pubkey,
+    pub timestamp: i64
+}
+
+#[event]
+pub struct VaultReclaimed {
+    pub creator_id: String,
+    pub amount: u64,
+    pub to_stakers: bool,
+    pub inactive_for: i64, // seconds since the profile's last activity
+    pub mint: Option<Pubkey>, // token swept along with the lamports, if any
+    pub token_amount: u64
+}
\ No newline at end of file
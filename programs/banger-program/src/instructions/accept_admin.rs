@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        require!(
+            self.config.pending_admin == Some(self.new_admin.key()),
+            CurveError::NotPendingAdmin
+        );
+
+        self.config.admin = self.new_admin.key();
+        self.config.pending_admin = None;
+
+        Ok(())
+    }
+}
\ No newline at end of file
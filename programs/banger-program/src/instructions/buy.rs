@@ -3,6 +3,7 @@ use anchor_spl::{
     metadata::Metadata,
     associated_token::AssociatedToken,
     token::{
+        self,
         Mint,
         Token,
         TokenAccount
@@ -11,7 +12,7 @@ use mpl_token_metadata::instructions::{
         MintV1Cpi, MintV1CpiAccounts, MintV1InstructionArgs
     };
 pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
-use crate::state::{Pool, Curve};
+use crate::state::{Pool, Curve, Config, Position, Observations, Referral, PAUSE_BUY};
 use crate::errors::CurveError;
 
 #[derive(Accounts)]
@@ -64,6 +65,71 @@ pub struct Buy<'info> {
     )]
     pub pool: Account<'info, Pool>,
 
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", pool.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.referrer.as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    // Quote token pools only
+    #[account(
+        constraint = pool.quote_mint == Some(quote_mint.key()) @ CurveError::MissingQuoteAccounts
+    )]
+    pub quote_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = buyer
+    )]
+    pub buyer_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_reserve: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator_vault
+    )]
+    pub creator_vault_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
     pub system_program: Program<'info, System>,
     pub metadata_program: Program<'info, Metadata>,
     pub token_program: Program<'info, Token>,
@@ -75,100 +141,45 @@ pub struct Buy<'info> {
 }
 
 impl<'info> Buy<'info> {
-    pub fn buy(&mut self, amount_in: u64, num_mint: u64) -> Result<()> {
-
-        let current_supply = self.mint.supply;
-
-        let mut subtotal: u64 = 0;
+    pub fn buy(&mut self, amount_in: u64, num_mint: u64, bumps: &BuyBumps) -> Result<()> {
 
-        for i in 0..num_mint {
-            let supply = current_supply + i;
-            let supply_str = supply.to_string();
-            let supply_str_ref = supply_str.as_str();
-            msg!(supply_str_ref);
-            let price = supply
-                .checked_pow(self.curve.pow as u32).ok_or(CurveError::Overflow)?
-                .checked_div(self.curve.frac).ok_or(CurveError::Overflow)?;
+        self.pool.check_not_paused(&self.config, PAUSE_BUY)?;
 
-            subtotal = subtotal.checked_add(price).ok_or(CurveError::Overflow)?;
-        }
-        let subtotal1_str = subtotal.to_string();
-        let subtotal1_str_ref = subtotal1_str.as_ref();
-        msg!(subtotal1_str_ref);
+        self.position.init_if_needed(self.buyer.key(), self.pool.key(), bumps.position);
+        self.pool.check_launch_limits(&mut self.position, num_mint, &Clock::get()?)?;
 
-        subtotal = subtotal.checked_mul(1_000_000_000).ok_or(CurveError::Overflow)?;
+        let current_supply = self.mint.supply;
 
-        let banger_fee = subtotal
-            .checked_mul(self.pool.banger_fee as u64).ok_or(CurveError::Overflow)?
-            .checked_div(10000).ok_or(CurveError::Overflow)?;
+        let lamports = self.curve.buy_price(current_supply, num_mint, self.mint.decimals)?;
+        let quote_decimals = self.quote_mint.as_ref().map(|m| m.decimals);
+        let subtotal = self.pool.to_quote(lamports, quote_decimals, true)?;
 
-        let creator_fee = subtotal
-            .checked_mul(self.pool.creator_fee as u64).ok_or(CurveError::Overflow)?
-            .checked_div(10000).ok_or(CurveError::Overflow)?;
+        let (creator_fee, banger_fee) = self.pool.fees(subtotal)?;
 
         let total = subtotal
             .checked_add(banger_fee).ok_or(CurveError::Overflow)?
             .checked_add(creator_fee).ok_or(CurveError::Overflow)?;
 
-        let current_supply_str = current_supply.to_string();
-        let current_supply_str_ref = current_supply_str.as_str();
-
-        let amount_in_str = amount_in.to_string();
-        let amount_in_str_ref = amount_in_str.as_str();
-
-        let num_mint_str = num_mint.to_string();
-        let num_mint_str_ref = num_mint_str.as_str();
-
-        let pow_str = self.curve.pow.to_string();
-        let pow_str_ref = pow_str.as_ref();
-
-        let frac_str = self.curve.frac.to_string();
-        let frac_str_ref = frac_str.as_ref();
-
-        let subtotal_str = subtotal.to_string();
-        let subtotal_str_ref = subtotal_str.as_ref();
-
-        let creator_fee_str = creator_fee.to_string();
-        let creator_fee_str_ref = creator_fee_str.as_ref();                
-        
-        let total_str = total.to_string();
-        let total_str_ref = total_str.as_ref();
-
-        msg!(current_supply_str_ref);
-        msg!(amount_in_str_ref);
-        msg!(num_mint_str_ref);
-        msg!(pow_str_ref);
-        msg!(frac_str_ref);
-        msg!(subtotal_str_ref);
-        msg!(creator_fee_str_ref);
-        msg!(total_str_ref);
-        require!(amount_in <= total, CurveError::Slippage);
-
-        // Transfer subtotal to pool
-        let accounts = Transfer {
-            from: self.buyer.to_account_info(),
-            to: self.pool.to_account_info()
-        };
-
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        msg!("supply: {}, num_mint: {}, subtotal: {}, total: {}", current_supply, num_mint, subtotal, total);
+        require!(total <= amount_in, CurveError::Slippage);
 
-        transfer(cpi_ctx, subtotal)?;
+        let staking_reward = self.pool.record_fees(creator_fee, banger_fee)?;
+        let creator_fee = creator_fee - staking_reward;
+        if self.position.balance == 0 {
+            self.pool.stats.holders = self.pool.stats.holders.checked_add(1).ok_or(CurveError::Overflow)?;
+        }
+        self.position.record_buy(num_mint, total, self.mint.decimals)?;
 
-        // Transfer creator fee
-        let accounts = Transfer {
-            from: self.buyer.to_account_info(),
-            to: self.creator_vault.to_account_info()
-        };
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
-        transfer(cpi_ctx, creator_fee)?;
+        let now = Clock::get()?.unix_timestamp;
+        let price = self.curve.spot_price(current_supply, self.mint.decimals)?;
+        let price = self.pool.to_quote(price, quote_decimals, false)?;
+        self.observations.record(now, price, subtotal)?;
+        self.pool.stats.record_trade(now, subtotal)?;
 
-        // Transfer Banger fee
-        let accounts = Transfer {
-            from: self.buyer.to_account_info(),
-            to: self.treasury.to_account_info()
-        };
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
-        transfer(cpi_ctx, banger_fee)?;
+        match self.pool.quote_mint {
+            Some(_) => self.pay_quote(subtotal + staking_reward, creator_fee, banger_fee)?,
+            None => self.pay_sol(subtotal + staking_reward, creator_fee, banger_fee)?
+        }
 
         let seeds = &[
             &b"authority"[..], 
@@ -209,7 +220,7 @@ impl<'info> Buy<'info> {
                 authorization_rules: None
             },
             MintV1InstructionArgs {
-                amount: num_mint as u64,
+                amount: num_mint,
                 authorization_data: None
             }
         );
@@ -218,4 +229,88 @@ impl<'info> Buy<'info> {
 
         Ok(())
     }
+
+    fn pay_sol(&mut self, reserve: u64, creator_fee: u64, banger_fee: u64) -> Result<()> {
+        let (treasury_fee, referral_fee) = match &self.referral {
+            Some(referral) => {
+                require_keys_neq!(referral.referrer, self.buyer.key(), CurveError::SelfReferral);
+                self.config.split_referral_fee(banger_fee)?
+            },
+            None => (banger_fee, 0)
+        };
+
+        // Transfer subtotal and stakers' share of the creator fee to pool
+        let accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: self.pool.to_account_info()
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(cpi_ctx, reserve)?;
+
+        // Transfer creator fee
+        let accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: self.creator_vault.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        transfer(cpi_ctx, creator_fee)?;
+
+        // Transfer Banger fee
+        let accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: self.treasury.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        transfer(cpi_ctx, treasury_fee)?;
+
+        // Transfer referral share of the Banger fee
+        if let Some(referral) = &mut self.referral {
+            let accounts = Transfer {
+                from: self.buyer.to_account_info(),
+                to: referral.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(cpi_ctx, referral_fee)?;
+
+            referral.accrue(referral_fee)?;
+            self.pool.record_referral_fee(referral_fee)?;
+        }
+
+        Ok(())
+    }
+
+    // Referrals are only paid on SOL pools
+    fn pay_quote(&self, reserve: u64, creator_fee: u64, banger_fee: u64) -> Result<()> {
+        require!(self.referral.is_none(), CurveError::QuotePoolUnsupported);
+
+        let (Some(_), Some(buyer_quote_ata), Some(quote_reserve), Some(treasury_quote_ata), Some(creator_vault_quote_ata)) = (
+            &self.quote_mint,
+            &self.buyer_quote_ata,
+            &self.quote_reserve,
+            &self.treasury_quote_ata,
+            &self.creator_vault_quote_ata
+        ) else {
+            return err!(CurveError::MissingQuoteAccounts);
+        };
+
+        let payments = [
+            (quote_reserve, reserve),
+            (creator_vault_quote_ata, creator_fee),
+            (treasury_quote_ata, banger_fee)
+        ];
+
+        for (to, amount) in payments {
+            let accounts = token::Transfer {
+                from: buyer_quote_ata.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.buyer.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,320 @@
+use anchor_lang::{prelude::*, system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer}};
+use anchor_spl::{
+    metadata::Metadata,
+    associated_token::AssociatedToken,
+    token::{
+        Mint,
+        Token
+    }};
+use mpl_token_metadata::instructions::{
+        MintV1Cpi, MintV1CpiAccounts, MintV1InstructionArgs
+    };
+pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use crate::state::{Pool, Curve, Config, Position, Observations, Referral, PAUSE_BUY, CURRENT_VERSION, RESERVED_SPACE};
+use crate::errors::CurveError;
+
+/// Accounts passed per leg in `remaining_accounts`, in this order:
+/// pool, mint, metadata, creator vault, buyer ATA, buyer position, observations.
+pub const BASKET_LEG_ACCOUNTS: usize = 7;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BasketLeg {
+    pub num_mint: u64,
+    pub amount_in: u64
+}
+
+#[derive(Accounts)]
+pub struct BuyBasket<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: used for signing
+    #[account(
+        mut,
+        seeds = [b"authority"],
+        bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    pub curve: Account<'info, Curve>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.referrer.as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(address = INSTRUCTIONS_ID)]
+    /// CHECK: no need to check
+    pub sysvar_instructions: UncheckedAccount<'info>
+}
+
+impl<'info> BuyBasket<'info> {
+    pub fn buy_basket(
+        &mut self,
+        legs: Vec<BasketLeg>,
+        amount_in: u64,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        bumps: &BuyBasketBumps
+    ) -> Result<()> {
+        require!(
+            !legs.is_empty() && remaining_accounts.len() == legs.len() * BASKET_LEG_ACCOUNTS,
+            CurveError::BasketAccountsMismatch
+        );
+
+        if let Some(referral) = &self.referral {
+            require_keys_neq!(referral.referrer, self.buyer.key(), CurveError::SelfReferral);
+        }
+
+        let mut spent: u64 = 0;
+        for (leg, accounts) in legs.iter().zip(remaining_accounts.chunks(BASKET_LEG_ACCOUNTS)) {
+            let total = self.buy_leg(leg, accounts, bumps)?;
+            spent = spent.checked_add(total).ok_or(CurveError::Overflow)?;
+        }
+
+        msg!("basket legs: {}, spent: {}", legs.len(), spent);
+        require!(spent <= amount_in, CurveError::Slippage);
+
+        Ok(())
+    }
+
+    // Each leg is written back before the next one is loaded, so the same pool
+    // can appear twice
+    fn buy_leg(
+        &mut self,
+        leg: &BasketLeg,
+        accounts: &'info [AccountInfo<'info>],
+        bumps: &BuyBasketBumps
+    ) -> Result<u64> {
+        let [pool_info, mint_info, metadata, creator_vault, buyer_ata, position_info, observations_info] = accounts else {
+            return err!(CurveError::BasketAccountsMismatch);
+        };
+
+        let mut pool = Box::new(Account::<Pool>::try_from(pool_info)?);
+        let mint = Account::<Mint>::try_from(mint_info)?;
+
+        let pool_address = Pubkey::create_program_address(
+            &[b"pool", mint.key().as_ref(), &[pool.bump]],
+            &crate::ID
+        ).map_err(|_| CurveError::BasketAccountsMismatch)?;
+        require_keys_eq!(pool_address, pool.key(), CurveError::BasketAccountsMismatch);
+        require_keys_eq!(pool.mint, mint.key(), CurveError::BasketAccountsMismatch);
+        require_keys_eq!(pool.curve, self.curve.key(), CurveError::BasketAccountsMismatch);
+        require_keys_eq!(pool.treasury, self.treasury.key(), CurveError::BasketAccountsMismatch);
+
+        let (vault_address, _) = Pubkey::find_program_address(
+            &[b"creator_vault", pool.creator_id.as_bytes()],
+            &crate::ID
+        );
+        require_keys_eq!(vault_address, creator_vault.key(), CurveError::BasketAccountsMismatch);
+
+        let mut observations = Box::new(Account::<Observations>::try_from(observations_info)?);
+        require_keys_eq!(observations.pool, pool.key(), CurveError::BasketAccountsMismatch);
+
+        let mut position = self.load_position(&pool, position_info)?;
+
+        pool.check_not_paused(&self.config, PAUSE_BUY)?;
+        pool.check_sol_quoted()?;
+
+        let clock = Clock::get()?;
+        pool.check_launch_limits(&mut position, leg.num_mint, &clock)?;
+
+        let current_supply = mint.supply;
+
+        let subtotal = self.curve.buy_price(current_supply, leg.num_mint, mint.decimals)?;
+
+        let (creator_fee, banger_fee) = pool.fees(subtotal)?;
+
+        let total = subtotal
+            .checked_add(banger_fee).ok_or(CurveError::Overflow)?
+            .checked_add(creator_fee).ok_or(CurveError::Overflow)?;
+
+        msg!("pool: {}, num_mint: {}, total: {}", pool.key(), leg.num_mint, total);
+        require!(total <= leg.amount_in, CurveError::Slippage);
+
+        let staking_reward = pool.record_fees(creator_fee, banger_fee)?;
+        let creator_fee = creator_fee - staking_reward;
+        if position.balance == 0 {
+            pool.stats.holders = pool.stats.holders.checked_add(1).ok_or(CurveError::Overflow)?;
+        }
+        position.record_buy(leg.num_mint, total, mint.decimals)?;
+
+        let price = self.curve.spot_price(current_supply, mint.decimals)?;
+        observations.record(clock.unix_timestamp, price, subtotal)?;
+        pool.stats.record_trade(clock.unix_timestamp, subtotal)?;
+
+        let (treasury_fee, referral_fee) = match &self.referral {
+            Some(_) => self.config.split_referral_fee(banger_fee)?,
+            None => (banger_fee, 0)
+        };
+
+        self.pay(pool_info, subtotal + staking_reward)?;
+        self.pay(creator_vault, creator_fee)?;
+        self.pay(&self.treasury.to_account_info(), treasury_fee)?;
+
+        if let Some(referral) = &mut self.referral {
+            let accounts = Transfer {
+                from: self.buyer.to_account_info(),
+                to: referral.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(cpi_ctx, referral_fee)?;
+
+            referral.accrue(referral_fee)?;
+            pool.record_referral_fee(referral_fee)?;
+        }
+
+        let seeds = &[
+            &b"authority"[..],
+            &[bumps.authority]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let token_owner = &self.buyer.to_account_info();
+        let authority = &self.authority.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let spl_token_program = &self.token_program.to_account_info();
+        let spl_ata_program = &self.associated_token_program.to_account_info();
+
+        // Mint token to buyer, metaplex creates the ATA if needed
+        let mint_tokens = MintV1Cpi::new(
+            metadata_program,
+            MintV1CpiAccounts {
+                token: buyer_ata,
+                token_owner: Some(token_owner),
+                metadata,
+                master_edition: None,
+                token_record: None,
+                mint: mint_info,
+                authority,
+                delegate_record: None,
+                payer: token_owner,
+                system_program,
+                sysvar_instructions,
+                spl_token_program,
+                spl_ata_program,
+                authorization_rules_program: None,
+                authorization_rules: None
+            },
+            MintV1InstructionArgs {
+                amount: leg.num_mint,
+                authorization_data: None
+            }
+        );
+        mint_tokens.invoke_signed(signer_seeds)?;
+
+        pool.exit(&crate::ID)?;
+        position.exit(&crate::ID)?;
+        observations.exit(&crate::ID)?;
+
+        Ok(total)
+    }
+
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: to.clone()
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+
+    // Positions can't be `init_if_needed` from remaining accounts, so create them by hand
+    fn load_position(
+        &self,
+        pool: &Account<'info, Pool>,
+        info: &'info AccountInfo<'info>
+    ) -> Result<Account<'info, Position>> {
+        let pool_key = pool.key();
+        let buyer_key = self.buyer.key();
+
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"position", pool_key.as_ref(), buyer_key.as_ref()],
+            &crate::ID
+        );
+        require_keys_eq!(address, info.key(), CurveError::BasketAccountsMismatch);
+
+        if info.data_is_empty() {
+            let seeds = &[
+                &b"position"[..],
+                pool_key.as_ref(),
+                buyer_key.as_ref(),
+                &[bump]
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            // Like Anchor's `init`, top up an address someone already sent lamports to
+            // instead of failing to create it
+            let space = 8 + Position::INIT_SPACE;
+            let rent = Rent::get()?.minimum_balance(space);
+            let system_program = self.system_program.to_account_info();
+
+            if info.lamports() == 0 {
+                let accounts = CreateAccount {
+                    from: self.buyer.to_account_info(),
+                    to: info.clone()
+                };
+                let cpi_ctx = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+                create_account(cpi_ctx, rent, space as u64, &crate::ID)?;
+            } else {
+                let shortfall = rent.saturating_sub(info.lamports());
+                if shortfall > 0 {
+                    let accounts = Transfer {
+                        from: self.buyer.to_account_info(),
+                        to: info.clone()
+                    };
+                    let cpi_ctx = CpiContext::new(system_program.clone(), accounts);
+                    transfer(cpi_ctx, shortfall)?;
+                }
+
+                let accounts = Allocate { account_to_allocate: info.clone() };
+                let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), accounts, signer_seeds);
+                allocate(cpi_ctx, space as u64)?;
+
+                let accounts = Assign { account_to_assign: info.clone() };
+                let cpi_ctx = CpiContext::new_with_signer(system_program, accounts, signer_seeds);
+                assign(cpi_ctx, &crate::ID)?;
+            }
+
+            let position = Position {
+                version: CURRENT_VERSION,
+                owner: buyer_key,
+                pool: pool_key,
+                balance: 0,
+                cost_basis: 0,
+                avg_entry_price: 0,
+                spent: 0,
+                received: 0,
+                realized_pnl: 0,
+                launch_bought: 0,
+                bump,
+                reserved: [0; RESERVED_SPACE]
+            };
+            position.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+
+        Account::try_from(info)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    close_account,
+    transfer,
+    CloseAccount,
+    Mint,
+    Token,
+    TokenAccount,
+    Transfer
+};
+use crate::state::{Order, OrderSide};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    // No pool, so orders can still be cancelled once it's closed
+    #[account(
+        mut,
+        seeds = [b"order", order.pool.as_ref(), owner.key().as_ref(), order.id.to_le_bytes().as_ref()],
+        bump = order.bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(
+        mut,
+        seeds = [b"order_escrow", order.key().as_ref()],
+        bump
+    )]
+    pub order_escrow: Option<Account<'info, TokenAccount>>,
+
+    // Sell orders only, to get the escrowed tokens back
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_ata: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: used for signing
+    #[account(
+        seeds = [b"authority"],
+        bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>
+}
+
+impl<'info> CancelOrder<'info> {
+    // Closing the order returns the escrowed lamports, sell orders also get their tokens back
+    pub fn cancel_order(&mut self, bumps: &CancelOrderBumps) -> Result<()> {
+        let Some(escrow) = &self.order_escrow else {
+            require!(self.order.side == OrderSide::Buy, CurveError::MissingOrderEscrow);
+            return Ok(());
+        };
+        let owner_ata = self.owner_ata.as_ref().ok_or(CurveError::MissingOwnerAta)?;
+
+        let seeds = &[
+            &b"authority"[..],
+            &[bumps.authority]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: escrow.to_account_info(),
+            to: owner_ata.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+        transfer(cpi_ctx, escrow.amount)?;
+
+        let accounts = CloseAccount {
+            account: escrow.to_account_info(),
+            destination: self.owner.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+        close_account(cpi_ctx)
+    }
+}
\ No newline at end of file
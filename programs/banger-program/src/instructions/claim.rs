@@ -3,6 +3,8 @@
 // seeds = [b"creator_vault", creator_id]
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{Transfer, transfer};
+use crate::state::{Config, CreatorProfile, PAUSE_CLAIM};
+use crate::errors::CurveError;
 
 #[derive(Accounts)]
 #[instruction(creator_id: String)]
@@ -10,6 +12,14 @@ pub struct Claim<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"creator", creator_id.as_bytes()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == Some(creator.key()) @ CurveError::NotCreator
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
     /// CHECK: ok
     #[account(
         mut,
@@ -18,11 +28,21 @@ pub struct Claim<'info> {
     )]
     pub creator_vault: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
     pub system_program: Program<'info, System>
 }
 
 impl<'info> Claim<'info> {
-    pub fn claim(&mut self, creator_id: String) -> Result<()> {
+    pub fn claim(&mut self, creator_id: String, bumps: &ClaimBumps) -> Result<()> {
+        self.config.check_not_paused(PAUSE_CLAIM)?;
+
+        self.creator_profile.last_activity = Clock::get()?.unix_timestamp;
+
         let cpi_accounts = Transfer {
             from: self.creator_vault.to_account_info(),
             to: self.creator.to_account_info()
@@ -30,12 +50,17 @@ impl<'info> Claim<'info> {
 
         let seeds = &[
             &b"creator_vault"[..],
-            &creator_id.as_bytes()[..],
+            creator_id.as_bytes(),
+            &[bumps.creator_vault]
         ];
         let signer_seeds = &[&seeds[..]];
 
         let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, signer_seeds);
 
-        transfer(cpi_ctx, self.creator_vault.lamports())
+        // The vault stays rent exempt to keep receiving fees
+        let amount = self.creator_vault.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+
+        transfer(cpi_ctx, amount)
     }
 }
\ No newline at end of file
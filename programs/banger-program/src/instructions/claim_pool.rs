@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, CreatorProfile};
+use crate::errors::CurveError;
+
+// The linked creator takes over a pool that was created on their behalf
+#[derive(Accounts)]
+pub struct ClaimPool<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", pool.creator_id.as_bytes()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == Some(creator.key()) @ CurveError::NotCreator,
+        constraint = creator_profile.mint == Some(pool.mint) @ CurveError::NotCreatorPool
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>
+}
+
+impl<'info> ClaimPool<'info> {
+    pub fn claim_pool(&mut self) -> Result<()> {
+        self.pool.admin = self.creator.key();
+        self.pool.verified = true;
+        self.creator_profile.last_activity = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Referral, PAUSE_CLAIM};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct ClaimReferral<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump = referral.bump,
+        has_one = referrer
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>
+}
+
+impl<'info> ClaimReferral<'info> {
+    pub fn claim_referral(&mut self) -> Result<()> {
+        self.config.check_not_paused(PAUSE_CLAIM)?;
+
+        // Everything above rent exemption is accrued fees
+        let rent = Rent::get()?.minimum_balance(8 + Referral::INIT_SPACE);
+        let amount = self.referral.get_lamports()
+            .checked_sub(rent).ok_or(CurveError::Overflow)?;
+
+        **self.referral.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **self.referrer.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        self.referral.claimed = self.referral.claimed.checked_add(amount).ok_or(CurveError::Overflow)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
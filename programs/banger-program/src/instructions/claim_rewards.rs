@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::{Config, Pool, Stake, PAUSE_CLAIM};
+use crate::errors::CurveError;
+
+// Staking rewards are held alongside the reserve, in the pool account or
+// the quote reserve for quote token pools
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake.bump,
+        has_one = owner
+    )]
+    pub stake: Box<Account<'info, Stake>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    // Quote token pools only
+    #[account(
+        mut,
+        seeds = [b"quote_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_reserve: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_mint == Some(owner_quote_ata.mint) @ CurveError::MissingQuoteAccounts,
+        token::authority = owner
+    )]
+    pub owner_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>
+}
+
+impl<'info> ClaimRewards<'info> {
+    pub fn claim_rewards(&mut self) -> Result<()> {
+        self.pool.check_not_paused(&self.config, PAUSE_CLAIM)?;
+
+        self.stake.settle(self.pool.reward_per_share)?;
+
+        let amount = self.stake.pending;
+        self.stake.pending = 0;
+        self.stake.claimed = self.stake.claimed.checked_add(amount).ok_or(CurveError::Overflow)?;
+
+        // Empty stake accounts are closed so they stop holding the pool open
+        if self.stake.is_empty() {
+            self.pool.stakers = self.pool.stakers.checked_sub(1).ok_or(CurveError::Overflow)?;
+            self.stake.close(self.owner.to_account_info())?;
+        }
+
+        if self.pool.quote_mint.is_none() {
+            **self.pool.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **self.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+            return Ok(());
+        }
+
+        let (Some(quote_reserve), Some(owner_quote_ata)) = (&self.quote_reserve, &self.owner_quote_ata) else {
+            return err!(CurveError::MissingQuoteAccounts);
+        };
+
+        let seeds = &[
+            &b"pool"[..],
+            self.pool.mint.as_ref(),
+            &[self.pool.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = token::Transfer {
+            from: quote_reserve.to_account_info(),
+            to: owner_quote_ata.to_account_info(),
+            authority: self.pool.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        token::transfer(cpi_ctx, amount)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        transfer,
+        Mint,
+        Token,
+        TokenAccount,
+        Transfer
+    }};
+use crate::state::{Config, CreatorProfile, PAUSE_CLAIM};
+use crate::errors::CurveError;
+
+// Token counterpart of `claim`, empties the creator vault's ATA for one mint
+#[derive(Accounts)]
+#[instruction(creator_id: String)]
+pub struct ClaimTokens<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", creator_id.as_bytes()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == Some(creator.key()) @ CurveError::NotCreator
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    #[account(
+        seeds = [b"creator_vault", creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault: SystemAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = creator_vault
+    )]
+    pub vault_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator
+    )]
+    pub creator_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> ClaimTokens<'info> {
+    pub fn claim_tokens(&mut self, creator_id: String, bumps: &ClaimTokensBumps) -> Result<()> {
+        self.config.check_not_paused(PAUSE_CLAIM)?;
+
+        self.creator_profile.last_activity = Clock::get()?.unix_timestamp;
+
+        let seeds = &[
+            &b"creator_vault"[..],
+            creator_id.as_bytes(),
+            &[bumps.creator_vault]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: self.vault_ata.to_account_info(),
+            to: self.creator_ata.to_account_info(),
+            authority: self.creator_vault.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        transfer(cpi_ctx, self.vault_ata.amount)
+    }
+}
\ No newline at end of file
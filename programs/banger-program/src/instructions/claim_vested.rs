@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        transfer,
+        Mint,
+        Token,
+        TokenAccount,
+        Transfer
+    }};
+use crate::state::{Config, VestingSchedule, PAUSE_CLAIM};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", mint.key().as_ref(), beneficiary.key().as_ref(), vesting.funder.as_ref()],
+        bump = vesting.bump,
+        has_one = beneficiary,
+        has_one = mint
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting_escrow", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary
+    )]
+    pub beneficiary_ata: Account<'info, TokenAccount>,
+
+    /// CHECK: used for signing
+    #[account(
+        seeds = [b"authority"],
+        bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn claim_vested(&mut self, bumps: &ClaimVestedBumps) -> Result<()> {
+        self.config.check_not_paused(PAUSE_CLAIM)?;
+
+        let vested = self.vesting.vested_amount(Clock::get()?.unix_timestamp)?;
+        let amount = vested.checked_sub(self.vesting.claimed).ok_or(CurveError::Overflow)?;
+
+        self.vesting.claimed = vested;
+
+        let seeds = &[
+            &b"authority"[..],
+            &[bumps.authority]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: self.vesting_escrow.to_account_info(),
+            to: self.beneficiary_ata.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::DcaPlan;
+
+// Closing returns the unspent deposit along with the rent
+#[derive(Accounts)]
+pub struct CloseDca<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dca", dca_plan.pool.as_ref(), owner.key().as_ref()],
+        bump = dca_plan.bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub dca_plan: Account<'info, DcaPlan>
+}
+
+impl<'info> CloseDca<'info> {
+    pub fn close_dca(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
\ No newline at end of file
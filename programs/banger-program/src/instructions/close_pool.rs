@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        self,
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use crate::state::{Config, CreatorProfile, Observations, Pool};
+use crate::events::PoolClosed;
+use crate::errors::CurveError;
+
+// Closes an empty pool with its observations, quote reserve and stake vault once
+// every staker has claimed. Reserve dust goes to whoever paid for the pool along
+// with the rent, and the creator profile is freed up for a new pool. Orders and
+// DCA plans can still be cancelled afterwards.
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    #[account(
+        mut,
+        constraint = creator_profile.wallet == Some(closer.key()) || closer.key() == config.admin @ CurveError::NotPoolCloser
+    )]
+    pub closer: Signer<'info>,
+
+    /// CHECK: refunded the rent
+    #[account(
+        mut,
+        address = pool.payer
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = mint,
+        close = payer
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump = observations.bump,
+        close = payer
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", pool.creator_id.as_bytes()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: only a token account if anyone ever staked, closed then
+    #[account(
+        mut,
+        seeds = [b"stake_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: UncheckedAccount<'info>,
+
+    /// CHECK: owns the stake vault
+    #[account(
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    // Quote token pools only
+    #[account(
+        constraint = pool.quote_mint == Some(quote_mint.key()) @ CurveError::MissingQuoteAccounts
+    )]
+    pub quote_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_reserve: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = closer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = payer
+    )]
+    pub payer_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> ClosePool<'info> {
+    pub fn close_pool(&mut self) -> Result<()> {
+        require!(self.mint.supply == 0, CurveError::PoolNotEmpty);
+        require!(self.pool.stakers == 0, CurveError::PoolHasStakers);
+
+        if self.pool.quote_mint.is_some() {
+            self.close_quote_reserve()?;
+        }
+
+        // Supply is zero, so the vault is empty
+        if self.stake_vault.owner == &token::ID {
+            self.close_stake_vault()?;
+        }
+
+        if self.creator_profile.mint == Some(self.mint.key()) {
+            self.creator_profile.mint = None;
+        }
+
+        emit!(PoolClosed {
+            pool: self.pool.key(),
+            mint: self.mint.key(),
+            creator_id: self.pool.creator_id.clone(),
+            closed_by: self.closer.key(),
+            timestamp: Clock::get()?.unix_timestamp
+        });
+
+        Ok(())
+    }
+
+    fn close_quote_reserve(&self) -> Result<()> {
+        let (Some(_), Some(quote_reserve), Some(payer_quote_ata)) = (
+            &self.quote_mint,
+            &self.quote_reserve,
+            &self.payer_quote_ata
+        ) else {
+            return err!(CurveError::MissingQuoteAccounts);
+        };
+
+        let mint_key = self.mint.key();
+        let seeds = &[
+            &b"pool"[..],
+            mint_key.as_ref(),
+            &[self.pool.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = token::Transfer {
+            from: quote_reserve.to_account_info(),
+            to: payer_quote_ata.to_account_info(),
+            authority: self.pool.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+        token::transfer(cpi_ctx, quote_reserve.amount)?;
+
+        let accounts = token::CloseAccount {
+            account: quote_reserve.to_account_info(),
+            destination: self.payer.to_account_info(),
+            authority: self.pool.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+        token::close_account(cpi_ctx)
+    }
+
+    fn close_stake_vault(&self) -> Result<()> {
+        let seeds = &[
+            &b"authority"[..],
+            &[self.pool.authority_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = token::CloseAccount {
+            account: self.stake_vault.to_account_info(),
+            destination: self.payer.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+        token::close_account(cpi_ctx)
+    }
+}
\ No newline at end of file
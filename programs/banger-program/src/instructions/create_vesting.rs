@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    transfer,
+    Mint,
+    Token,
+    TokenAccount,
+    Transfer
+};
+use crate::state::{Pool, VestingSchedule, CURRENT_VERSION, RESERVED_SPACE};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateVesting<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder
+    )]
+    pub funder_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [b"vesting", mint.key().as_ref(), beneficiary.as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(
+        init,
+        payer = funder,
+        token::mint = mint,
+        token::authority = authority,
+        seeds = [b"vesting_escrow", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+
+    /// CHECK: custody of the escrow
+    #[account(
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    // Only Banger pool mints can be vested
+    #[account(
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>
+}
+
+impl<'info> CreateVesting<'info> {
+    pub fn create_vesting(
+        &mut self,
+        beneficiary: Pubkey,
+        total: u64,
+        start: i64,
+        cliff: i64,
+        duration: i64,
+        bumps: &CreateVestingBumps
+    ) -> Result<()> {
+        require!(duration > 0, CurveError::InvalidVestingDuration);
+        require!(
+            cliff >= start && cliff <= start.checked_add(duration).ok_or(CurveError::Overflow)?,
+            CurveError::InvalidVestingCliff
+        );
+
+        let accounts = Transfer {
+            from: self.funder_ata.to_account_info(),
+            to: self.vesting_escrow.to_account_info(),
+            authority: self.funder.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+        transfer(cpi_ctx, total)?;
+
+        self.vesting.set_inner(VestingSchedule {
+            version: CURRENT_VERSION,
+            beneficiary,
+            funder: self.funder.key(),
+            mint: self.mint.key(),
+            total,
+            claimed: 0,
+            start,
+            cliff,
+            duration,
+            revoked: false,
+            bump: bumps.vesting,
+            reserved: [0; RESERVED_SPACE]
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::Metadata,
+    associated_token::AssociatedToken,
+    token::{
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use mpl_token_metadata::instructions::{
+        MintV1Cpi, MintV1CpiAccounts, MintV1InstructionArgs
+    };
+pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use crate::state::{Pool, Curve, Config, Position, Observations, DcaPlan, PAUSE_BUY};
+use crate::errors::CurveError;
+
+// Permissionless crank, buys with one interval's worth of the plan's deposit
+#[derive(Accounts)]
+pub struct ExecuteDca<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dca", pool.key().as_ref(), owner.key().as_ref()],
+        bump = dca_plan.bump,
+        has_one = owner,
+        has_one = pool
+    )]
+    pub dca_plan: Box<Account<'info, DcaPlan>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: used for signing
+    #[account(
+        mut,
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: will be checked by metaplex
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub curve: Box<Account<'info, Curve>>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", pool.creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = curve,
+        has_one = treasury
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(address = INSTRUCTIONS_ID)]
+    /// CHECK: no need to check
+    pub sysvar_instructions: UncheckedAccount<'info>
+}
+
+impl<'info> ExecuteDca<'info> {
+    pub fn execute_dca(&mut self) -> Result<()> {
+        self.pool.check_not_paused(&self.config, PAUSE_BUY)?;
+
+        let clock = Clock::get()?;
+        let next = self.dca_plan.last_executed
+            .checked_add(self.dca_plan.interval).ok_or(CurveError::Overflow)?;
+        require!(clock.unix_timestamp >= next, CurveError::DcaNotDue);
+
+        let supply = self.mint.supply;
+        let decimals = self.mint.decimals;
+
+        let price = self.curve.spot_price(supply, decimals)?;
+        require!(price <= self.dca_plan.max_price, CurveError::DcaPriceTooHigh);
+
+        let rent = Rent::get()?.minimum_balance(8 + DcaPlan::INIT_SPACE);
+        let available = self.dca_plan.get_lamports().saturating_sub(rent);
+        let budget = self.dca_plan.lamports_per_interval.min(available);
+
+        // Leave room for fees on top of the curve price
+        let fee_bps = (self.pool.creator_fee as u64) + (self.pool.banger_fee as u64);
+        let curve_budget = budget
+            .checked_mul(10000).ok_or(CurveError::Overflow)?
+            .checked_div(10000 + fee_bps).ok_or(CurveError::Overflow)?;
+
+        // Stop where the spot price would pass the plan's cap, not just check it before buying
+        let (affordable, _) = self.curve.max_buy(supply, curve_budget, decimals)?;
+        let amount = affordable.min(self.curve.max_mint_below(supply, self.dca_plan.max_price, decimals));
+        require!(amount > 0, CurveError::NothingToBuy);
+        let subtotal = self.curve.buy_price(supply, amount, decimals)?;
+
+        self.pool.check_launch_limits(&mut self.position, amount, &clock)?;
+
+        let (creator_fee, banger_fee) = self.pool.fees(subtotal)?;
+
+        let total = subtotal
+            .checked_add(banger_fee).ok_or(CurveError::Overflow)?
+            .checked_add(creator_fee).ok_or(CurveError::Overflow)?;
+
+        msg!("dca: {}, num_mint: {}, total: {}", self.dca_plan.key(), amount, total);
+
+        self.dca_plan.last_executed = clock.unix_timestamp;
+
+        let staking_reward = self.pool.record_fees(creator_fee, banger_fee)?;
+        let creator_fee = creator_fee - staking_reward;
+        if self.position.balance == 0 {
+            self.pool.stats.holders = self.pool.stats.holders.checked_add(1).ok_or(CurveError::Overflow)?;
+        }
+        self.position.record_buy(amount, total, decimals)?;
+
+        self.observations.record(clock.unix_timestamp, price, subtotal)?;
+        self.pool.stats.record_trade(clock.unix_timestamp, subtotal)?;
+
+        // Pay out of the plan's deposit
+        **self.dca_plan.to_account_info().try_borrow_mut_lamports()? -= total;
+        **self.pool.to_account_info().try_borrow_mut_lamports()? += subtotal + staking_reward;
+        **self.creator_vault.to_account_info().try_borrow_mut_lamports()? += creator_fee;
+        **self.treasury.to_account_info().try_borrow_mut_lamports()? += banger_fee;
+
+        let seeds = &[
+            &b"authority"[..],
+            &[self.pool.authority_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let token = &self.owner_ata.to_account_info();
+        let token_owner = &self.owner.to_account_info();
+        let metadata = &self.metadata.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let authority = &self.authority.to_account_info();
+        let payer = &self.cranker.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let spl_token_program = &self.token_program.to_account_info();
+        let spl_ata_program = &self.associated_token_program.to_account_info();
+
+        // Mint token to plan owner
+        let mint_tokens = MintV1Cpi::new(
+            metadata_program,
+            MintV1CpiAccounts {
+                token,
+                token_owner: Some(token_owner),
+                metadata,
+                master_edition: None,
+                token_record: None,
+                mint,
+                authority,
+                delegate_record: None,
+                payer,
+                system_program,
+                sysvar_instructions,
+                spl_token_program,
+                spl_ata_program,
+                authorization_rules_program: None,
+                authorization_rules: None
+            },
+            MintV1InstructionArgs {
+                amount,
+                authorization_data: None
+            }
+        );
+        mint_tokens.invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
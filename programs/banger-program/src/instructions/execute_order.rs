@@ -0,0 +1,322 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::Metadata,
+    associated_token::AssociatedToken,
+    token::{
+        close_account,
+        transfer,
+        CloseAccount,
+        Mint,
+        Token,
+        TokenAccount,
+        Transfer
+    }};
+use mpl_token_metadata::instructions::{
+        BurnV1Cpi, BurnV1CpiAccounts, BurnV1InstructionArgs,
+        MintV1Cpi, MintV1CpiAccounts, MintV1InstructionArgs
+    };
+pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use crate::state::{Pool, Curve, Config, Position, Observations, Order, OrderSide, PAUSE_BUY, PAUSE_SELL};
+use crate::errors::CurveError;
+
+// Permissionless, any keeper can fill an order once the curve price crosses its trigger
+#[derive(Accounts)]
+pub struct ExecuteOrder<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"order", pool.key().as_ref(), owner.key().as_ref(), order.id.to_le_bytes().as_ref()],
+        bump = order.bump,
+        has_one = owner,
+        has_one = pool,
+        close = owner
+    )]
+    pub order: Box<Account<'info, Order>>,
+
+    #[account(
+        mut,
+        seeds = [b"order_escrow", order.key().as_ref()],
+        bump
+    )]
+    pub order_escrow: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: used for signing
+    #[account(
+        mut,
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: will be checked by metaplex
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub curve: Box<Account<'info, Curve>>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", pool.creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = curve,
+        has_one = treasury
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(address = INSTRUCTIONS_ID)]
+    /// CHECK: no need to check
+    pub sysvar_instructions: UncheckedAccount<'info>
+}
+
+impl<'info> ExecuteOrder<'info> {
+    pub fn execute_order(&mut self) -> Result<()> {
+        let supply = self.mint.supply;
+        let decimals = self.mint.decimals;
+        let price = self.curve.spot_price(supply, decimals)?;
+
+        match self.order.side {
+            OrderSide::Buy => self.execute_buy(supply, decimals, price)?,
+            OrderSide::Sell => self.execute_sell(supply, decimals, price)?
+        }
+
+        // Tip the keeper, the rest of the order's lamports go back to the owner on close
+        let tip = self.order.keeper_tip;
+        **self.order.to_account_info().try_borrow_mut_lamports()? -= tip;
+        **self.keeper.to_account_info().try_borrow_mut_lamports()? += tip;
+
+        Ok(())
+    }
+
+    fn execute_buy(&mut self, supply: u64, decimals: u8, price: u64) -> Result<()> {
+        self.pool.check_not_paused(&self.config, PAUSE_BUY)?;
+        require!(price <= self.order.trigger_price, CurveError::TriggerNotReached);
+
+        let amount = self.order.amount;
+        let clock = Clock::get()?;
+        self.pool.check_launch_limits(&mut self.position, amount, &clock)?;
+
+        let subtotal = self.curve.buy_price(supply, amount, decimals)?;
+        let (creator_fee, banger_fee) = self.pool.fees(subtotal)?;
+
+        let total = subtotal
+            .checked_add(banger_fee).ok_or(CurveError::Overflow)?
+            .checked_add(creator_fee).ok_or(CurveError::Overflow)?;
+
+        msg!("order: {}, num_mint: {}, total: {}", self.order.key(), amount, total);
+        require!(total <= self.order.limit, CurveError::Slippage);
+
+        let staking_reward = self.pool.record_fees(creator_fee, banger_fee)?;
+        let creator_fee = creator_fee - staking_reward;
+        if self.position.balance == 0 {
+            self.pool.stats.holders = self.pool.stats.holders.checked_add(1).ok_or(CurveError::Overflow)?;
+        }
+        self.position.record_buy(amount, total, decimals)?;
+
+        self.observations.record(clock.unix_timestamp, price, subtotal)?;
+        self.pool.stats.record_trade(clock.unix_timestamp, subtotal)?;
+
+        // Pay out of the escrowed lamports
+        **self.order.to_account_info().try_borrow_mut_lamports()? -= total;
+        **self.pool.to_account_info().try_borrow_mut_lamports()? += subtotal + staking_reward;
+        **self.creator_vault.to_account_info().try_borrow_mut_lamports()? += creator_fee;
+        **self.treasury.to_account_info().try_borrow_mut_lamports()? += banger_fee;
+
+        let seeds = &[
+            &b"authority"[..],
+            &[self.pool.authority_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let token = &self.owner_ata.to_account_info();
+        let token_owner = &self.owner.to_account_info();
+        let metadata = &self.metadata.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let authority = &self.authority.to_account_info();
+        let payer = &self.keeper.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let spl_token_program = &self.token_program.to_account_info();
+        let spl_ata_program = &self.associated_token_program.to_account_info();
+
+        // Mint token to order owner
+        let mint_tokens = MintV1Cpi::new(
+            metadata_program,
+            MintV1CpiAccounts {
+                token,
+                token_owner: Some(token_owner),
+                metadata,
+                master_edition: None,
+                token_record: None,
+                mint,
+                authority,
+                delegate_record: None,
+                payer,
+                system_program,
+                sysvar_instructions,
+                spl_token_program,
+                spl_ata_program,
+                authorization_rules_program: None,
+                authorization_rules: None
+            },
+            MintV1InstructionArgs {
+                amount,
+                authorization_data: None
+            }
+        );
+        mint_tokens.invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    fn execute_sell(&mut self, supply: u64, decimals: u8, price: u64) -> Result<()> {
+        self.pool.check_not_paused(&self.config, PAUSE_SELL)?;
+        require!(price >= self.order.trigger_price, CurveError::TriggerNotReached);
+
+        let escrow = self.order_escrow.as_ref().ok_or(CurveError::MissingOrderEscrow)?;
+
+        let amount = self.order.amount;
+
+        let total = self.curve.sell_price(supply, amount, decimals)?;
+        let (creator_fee, banger_fee) = self.pool.fees(total)?;
+
+        let subtotal = total
+            .checked_sub(banger_fee).ok_or(CurveError::Overflow)?
+            .checked_sub(creator_fee).ok_or(CurveError::Overflow)?;
+
+        msg!("order: {}, num_burn: {}, subtotal: {}", self.order.key(), amount, subtotal);
+        require!(subtotal >= self.order.limit, CurveError::Slippage);
+
+        let staking_reward = self.pool.record_fees(creator_fee, banger_fee)?;
+        let creator_fee = creator_fee - staking_reward;
+
+        let was_holder = self.position.balance > 0;
+        self.position.record_sell(amount, subtotal, decimals)?;
+        if was_holder && self.position.balance == 0 {
+            self.pool.stats.holders = self.pool.stats.holders.saturating_sub(1);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        self.observations.record(now, price, total)?;
+        self.pool.stats.record_trade(now, total)?;
+
+        let seeds = &[
+            &b"authority"[..],
+            &[self.pool.authority_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let authority = &self.authority.to_account_info();
+        let metadata = &self.metadata.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let token = &escrow.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let spl_token_program = &self.token_program.to_account_info();
+
+        // Burn escrowed tokens
+        let burn_tokens = BurnV1Cpi::new(
+            metadata_program,
+            BurnV1CpiAccounts {
+                authority,
+                collection_metadata: None,
+                metadata,
+                edition: None,
+                mint,
+                token,
+                master_edition: None,
+                master_edition_mint: None,
+                master_edition_token: None,
+                edition_marker: None,
+                token_record: None,
+                system_program,
+                sysvar_instructions,
+                spl_token_program,
+            },
+            BurnV1InstructionArgs {
+                amount
+            }
+        );
+        burn_tokens.invoke_signed(signer_seeds)?;
+
+        // Anything sent to the escrow on top of the order goes back to the owner
+        let leftover = escrow.amount.saturating_sub(amount);
+        if leftover > 0 {
+            let accounts = Transfer {
+                from: escrow.to_account_info(),
+                to: self.owner_ata.to_account_info(),
+                authority: self.authority.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+            transfer(cpi_ctx, leftover)?;
+        }
+
+        // Return the escrow rent to the owner
+        let accounts = CloseAccount {
+            account: escrow.to_account_info(),
+            destination: self.owner.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+        close_account(cpi_ctx)?;
+
+        // Pay out after the token CPIs so no lamports move ahead of them
+        **self.pool.to_account_info().try_borrow_mut_lamports()? -= total - staking_reward;
+        **self.owner.to_account_info().try_borrow_mut_lamports()? += subtotal;
+        **self.creator_vault.to_account_info().try_borrow_mut_lamports()? += creator_fee;
+        **self.treasury.to_account_info().try_borrow_mut_lamports()? += banger_fee;
+
+        Ok(())
+    }
+}
\ No newline at end of file
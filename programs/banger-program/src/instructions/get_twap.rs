@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{Pool, Curve, Observations};
+use crate::errors::CurveError;
+
+// Read only, the TWAP per whole token in the pool's quote unit is returned as return data
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    pub mint: Account<'info, Mint>,
+
+    pub curve: Account<'info, Curve>,
+
+    #[account(
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = curve
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"observations", pool.key().as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    // Quote token pools only, for the decimals of the spot price
+    #[account(
+        constraint = pool.quote_mint == Some(quote_mint.key()) @ CurveError::MissingQuoteAccounts
+    )]
+    pub quote_mint: Option<Account<'info, Mint>>
+}
+
+impl<'info> GetTwap<'info> {
+    pub fn get_twap(&self, window: i64) -> Result<u64> {
+        let price = self.curve.spot_price(self.mint.supply, self.mint.decimals)?;
+        let price = self.pool.to_quote(price, self.quote_mint.as_ref().map(|m| m.decimals), false)?;
+
+        self.observations.twap(Clock::get()?.unix_timestamp, price, window)
+    }
+}
\ No newline at end of file
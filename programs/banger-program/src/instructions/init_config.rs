@@ -0,0 +1,72 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use crate::errors::CurveError;
+use crate::state::{Config, VaultExpiry, ReclaimTarget, CURRENT_VERSION, RESERVED_SPACE};
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    // Only the upgrade authority can create the config, so deployment can't be front-run
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CurveError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+impl<'info> InitConfig<'info> {
+    pub fn init_config(&mut self, bumps: &InitConfigBumps) -> Result<()> {
+        self.config.set_inner(Config {
+            version: CURRENT_VERSION,
+            admin: self.admin.key(),
+            pending_admin: None,
+            fee_manager: self.admin.key(),
+            pauser: self.admin.key(),
+            creator_verifier: self.admin.key(),
+            treasury_split: Vec::new(),
+            referral_fee: 0,
+            vault_expiry: VaultExpiry {
+                period: 0,
+                target: ReclaimTarget::Treasury
+            },
+            paused: 0,
+            bump: bumps.config,
+            reserved: [0; RESERVED_SPACE]
+        });
+
+        // Fund the treasury up to rent exemption, or fees below it couldn't be paid in
+        let lamports = Rent::get()?.minimum_balance(0).saturating_sub(self.treasury.lamports());
+        if lamports > 0 {
+            let accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: self.treasury.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(cpi_ctx, lamports)?;
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
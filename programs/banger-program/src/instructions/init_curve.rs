@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::Curve;
+use crate::state::{Curve, CURRENT_VERSION, RESERVED_SPACE};
 
 #[derive(Accounts)]
 pub struct InitCurve<'info> {
@@ -9,7 +9,7 @@ pub struct InitCurve<'info> {
     #[account(
         init,
         payer = admin,
-        space = Curve::INIT_SPACE,
+        space = 8 + Curve::INIT_SPACE,
         seeds = [b"curve"],
         bump
     )]
@@ -21,8 +21,10 @@ pub struct InitCurve<'info> {
 impl<'info> InitCurve<'info> {
     pub fn init_curve(&mut self, pow: u64, frac: u64) -> Result<()> {
         self.curve.set_inner(Curve {
+            version: CURRENT_VERSION,
             pow,
-            frac
+            frac,
+            reserved: [0; RESERVED_SPACE]
         });
 
         Ok(())
@@ -1,8 +1,9 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
 use anchor_spl::{
     metadata::Metadata, token::{
         Mint,
         Token,
+        TokenAccount
     }
 };
 
@@ -18,10 +19,11 @@ use mpl_token_metadata::{
     }
 };
 pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
-use crate::state::{Pool, Curve};
+use crate::state::{Pool, PoolStats, PAUSE_BUY, Curve, CreatorProfile, LaunchSettings, Observation, Observations, OBSERVATIONS_LEN, MAX_CREATOR_ID_LEN, MAX_DECIMALS, CURRENT_VERSION, RESERVED_SPACE};
+use crate::errors::CurveError;
 
 #[derive(Accounts)]
-#[instruction(creator_id: String)]
+#[instruction(creator_id: String, creator_fee: u16, banger_fee: u16, decimals: u8)]
 pub struct InitPool<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -29,7 +31,7 @@ pub struct InitPool<'info> {
     #[account(
         init,
         payer = admin,
-        mint::decimals = 0,
+        mint::decimals = decimals,
         mint::authority = authority
     )]
     pub mint: Account<'info, Mint>,
@@ -51,12 +53,42 @@ pub struct InitPool<'info> {
     #[account(
         init,
         payer = admin,
-        space = Pool::INIT_SPACE,
+        space = 8 + Pool::INIT_SPACE,
         seeds = [b"pool", mint.key().as_ref()],
         bump
     )]
     pub pool: Account<'info, Pool>,
 
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Observations::INIT_SPACE,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    // The creator's current pool, only passed by `launch_creator_pool` to replace an unclaimed one
+    #[account(
+        mut,
+        seeds = [b"pool", previous_pool.mint.as_ref()],
+        bump = previous_pool.bump
+    )]
+    pub previous_pool: Option<Box<Account<'info, Pool>>>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
     pub treasury: SystemAccount<'info>,
 
     #[account(
@@ -66,6 +98,19 @@ pub struct InitPool<'info> {
     )]
     pub creator_vault: SystemAccount<'info>,
 
+    // Pools priced in an SPL token pass its mint, SOL pools pass neither
+    pub quote_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"quote_reserve", pool.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = pool
+    )]
+    pub quote_reserve: Option<Box<Account<'info, TokenAccount>>>,
+
     system_program: Program<'info, System>,
     token_program: Program<'info, Token>,
     metadata_program: Program<'info, Metadata>,
@@ -76,16 +121,32 @@ pub struct InitPool<'info> {
 }
 
 impl<'info> InitPool<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn init_pool(
         &mut self,
         creator_id: String,
         creator_fee: u16,
         banger_fee: u16,
+        decimals: u8,
         token_name: String,
         token_metadata_uri: String,
+        launch: LaunchSettings,
         bumps: &InitPoolBumps
     ) -> Result<()> {
-        
+        require!(creator_id.len() <= MAX_CREATOR_ID_LEN, CurveError::CreatorIdTooLong);
+        require!(self.creator_profile.mint.is_none(), CurveError::PoolExists);
+        require!(decimals <= MAX_DECIMALS, CurveError::InvalidDecimals);
+        require!(
+            (creator_fee as u32) + (banger_fee as u32) <= 10000,
+            CurveError::InvalidFee
+        );
+        require!(
+            self.quote_mint.is_some() == self.quote_reserve.is_some(),
+            CurveError::MissingQuoteAccounts
+        );
+        // Make sure the curve can price every affordable supply with these decimals
+        self.curve.check_decimals(decimals)?;
+
         let metadata = &self.metadata.to_account_info();
         let mint = &self.mint.to_account_info();
         let authority = &self.authority.to_account_info();
@@ -97,7 +158,7 @@ impl<'info> InitPool<'info> {
 
         let creator = vec![
             Creator {
-                address: self.authority.key().clone(),
+                address: self.authority.key(),
                 verified: true,
                 share: 100,
             },
@@ -142,19 +203,114 @@ impl<'info> InitPool<'info> {
         metadata_account.invoke_signed(signer_seeds)?;
         msg!("Metadata Account created!");
 
+        // Pools stay unverified until the creator has linked a wallet and claimed them,
+        // unless the linked creator is the one creating the pool
+        let verified = self.creator_profile.wallet == Some(self.admin.key());
+
+        self.creator_profile.set_inner(CreatorProfile {
+            version: CURRENT_VERSION,
+            creator_id: creator_id.clone(),
+            mint: Some(self.mint.key()),
+            wallet: self.creator_profile.wallet,
+            total_sol_tips: self.creator_profile.total_sol_tips,
+            tip_count: self.creator_profile.tip_count,
+            last_activity: Clock::get()?.unix_timestamp,
+            bump: bumps.creator_profile,
+            reserved: self.creator_profile.reserved
+        });
+
+        let clock = Clock::get()?;
+
+        let mut observations = [Observation::default(); OBSERVATIONS_LEN];
+        observations[0].timestamp = clock.unix_timestamp;
+
+        self.observations.set_inner(Observations {
+            version: CURRENT_VERSION,
+            pool: self.pool.key(),
+            index: 0,
+            observations,
+            bump: bumps.observations,
+            reserved: [0; RESERVED_SPACE]
+        });
+
         // Initialize pool
         self.pool.set_inner(Pool {
+            version: CURRENT_VERSION,
             admin: self.admin.key(),
+            payer: self.admin.key(),
             mint: self.mint.key(),
             curve: self.curve.key(),
             treasury: self.treasury.key(),
+            quote_mint: self.quote_mint.as_ref().map(|mint| mint.key()),
             creator_id,
             creator_fee,
             banger_fee,
+            total_creator_fees: 0,
+            total_banger_fees: 0,
+            verified,
+            launch,
+            launch_slot: 0,
+            stats: PoolStats {
+                last_hour: clock.unix_timestamp / 3600,
+                ..PoolStats::default()
+            },
+            staking_share: 0,
+            total_staked: 0,
+            stakers: 0,
+            reward_per_share: 0,
+            holder_tiers: Vec::new(),
+            paused: 0,
             bump: bumps.pool,
-            authority_bump: bumps.authority
+            authority_bump: bumps.authority,
+            reserved: [0; RESERVED_SPACE]
         });
-        
+
+        // Fund the creator vault up to rent exemption, or fees below it couldn't be paid in
+        let lamports = Rent::get()?.minimum_balance(0).saturating_sub(self.creator_vault.lamports());
+        if lamports > 0 {
+            let accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: self.creator_vault.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(cpi_ctx, lamports)?;
+        }
+
         Ok(())
     }
+
+    // Same as `init_pool`, but only the creator's linked wallet can call it so the pool
+    // starts out verified
+    #[allow(clippy::too_many_arguments)]
+    pub fn launch_creator_pool(
+        &mut self,
+        creator_id: String,
+        creator_fee: u16,
+        banger_fee: u16,
+        decimals: u8,
+        token_name: String,
+        token_metadata_uri: String,
+        launch: LaunchSettings,
+        bumps: &InitPoolBumps
+    ) -> Result<()> {
+        require!(
+            self.creator_profile.wallet == Some(self.admin.key()),
+            CurveError::NotCreator
+        );
+
+        // A pool someone else created for this creator id and the creator never claimed
+        // doesn't hold the creator back. Its buys stop so only one pool keeps growing
+        // for the creator id, holders can still sell out of it.
+        if let Some(current) = self.creator_profile.mint {
+            let previous = self.previous_pool.as_mut().ok_or(CurveError::PoolExists)?;
+            require_keys_eq!(previous.mint, current, CurveError::PoolExists);
+            require!(!previous.verified, CurveError::PoolExists);
+
+            previous.paused |= PAUSE_BUY;
+
+            self.creator_profile.mint = None;
+        }
+
+        self.init_pool(creator_id, creator_fee, banger_fee, decimals, token_name, token_metadata_uri, launch, bumps)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{Referral, CURRENT_VERSION, RESERVED_SPACE};
+
+#[derive(Accounts)]
+pub struct InitReferral<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub system_program: Program<'info, System>
+}
+
+impl<'info> InitReferral<'info> {
+    pub fn init_referral(&mut self, bumps: &InitReferralBumps) -> Result<()> {
+        self.referral.set_inner(Referral {
+            version: CURRENT_VERSION,
+            referrer: self.referrer.key(),
+            accrued: 0,
+            claimed: 0,
+            bump: bumps.referral,
+            reserved: [0; RESERVED_SPACE]
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, CreatorProfile, MAX_CREATOR_ID_LEN, CURRENT_VERSION};
+use crate::errors::CurveError;
+
+// The creator verifier attests off-chain that `wallet` belongs to `creator_id`
+#[derive(Accounts)]
+#[instruction(creator_id: String)]
+pub struct LinkCreator<'info> {
+    #[account(mut)]
+    pub creator_verifier: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = creator_verifier
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = creator_verifier,
+        space = 8 + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    pub system_program: Program<'info, System>
+}
+
+impl<'info> LinkCreator<'info> {
+    pub fn link_creator(
+        &mut self,
+        creator_id: String,
+        wallet: Pubkey,
+        bumps: &LinkCreatorBumps
+    ) -> Result<()> {
+        require!(creator_id.len() <= MAX_CREATOR_ID_LEN, CurveError::CreatorIdTooLong);
+
+        self.creator_profile.set_inner(CreatorProfile {
+            version: CURRENT_VERSION,
+            creator_id,
+            mint: self.creator_profile.mint,
+            wallet: Some(wallet),
+            total_sol_tips: self.creator_profile.total_sol_tips,
+            tip_count: self.creator_profile.tip_count,
+            last_activity: Clock::get()?.unix_timestamp,
+            bump: bumps.creator_profile,
+            reserved: self.creator_profile.reserved
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}, Discriminator};
+use anchor_spl::token::Mint;
+use crate::state::{
+    Config,
+    CreatorProfile,
+    Curve,
+    CurveV0,
+    LaunchSettings,
+    Observation,
+    Observations,
+    Pool,
+    PoolStats,
+    PoolV0,
+    CURRENT_VERSION,
+    OBSERVATIONS_LEN,
+    RESERVED_SPACE
+};
+use crate::errors::CurveError;
+
+// Moves a pool created before accounts were versioned, and its curve if that
+// hasn't been migrated yet, to the current layout. New fields get the same
+// defaults as a freshly created pool, and the pool takes its creator id's
+// profile unless a newer pool already has it.
+#[derive(Accounts)]
+#[instruction(creator_id: String)]
+pub struct MigratePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    // Legacy pools paid treasury fees to a wallet, migrated ones pay the treasury PDA
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: may still have the legacy layout, checked in the handler
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: may still have the legacy layout, checked in the handler
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub curve: UncheckedAccount<'info>,
+
+    // Checked against the legacy pool in the handler
+    pub mint: Box<Account<'info, Mint>>,
+
+    // Legacy pools predate observations
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Observations::INIT_SPACE,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    // Legacy pools predate profiles too, `creator_id` has to match the pool's
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    pub system_program: Program<'info, System>
+}
+
+impl<'info> MigratePool<'info> {
+    pub fn migrate_pool(&mut self, creator_id: String, bumps: &MigratePoolBumps) -> Result<()> {
+        let old = {
+            let data = self.pool.try_borrow_data()?;
+            require!(data.len() == PoolV0::SPACE, CurveError::AlreadyMigrated);
+            require!(data[..8] == Pool::DISCRIMINATOR, CurveError::InvalidLegacyAccount);
+            PoolV0::deserialize(&mut &data[8..])?
+        };
+
+        let pool_key = Pubkey::create_program_address(
+            &[b"pool", old.mint.as_ref(), &[old.bump]],
+            &crate::ID
+        ).map_err(|_| CurveError::InvalidLegacyAccount)?;
+        require_keys_eq!(pool_key, self.pool.key(), CurveError::InvalidLegacyAccount);
+        require_keys_eq!(old.curve, self.curve.key(), CurveError::InvalidLegacyAccount);
+        require_keys_eq!(old.mint, self.mint.key(), CurveError::InvalidLegacyAccount);
+        require!(old.creator_id == creator_id, CurveError::InvalidLegacyAccount);
+
+        let clock = Clock::get()?;
+
+        let pool = Pool {
+            version: CURRENT_VERSION,
+            admin: old.admin,
+            payer: old.admin,
+            mint: old.mint,
+            curve: old.curve,
+            treasury: self.treasury.key(),
+            quote_mint: None,
+            creator_id: old.creator_id,
+            creator_fee: old.creator_fee,
+            banger_fee: old.banger_fee,
+            total_creator_fees: 0,
+            total_banger_fees: 0,
+            verified: false,
+            launch: LaunchSettings::default(),
+            launch_slot: 0,
+            stats: PoolStats {
+                last_hour: clock.unix_timestamp / 3600,
+                ..PoolStats::default()
+            },
+            staking_share: 0,
+            total_staked: 0,
+            stakers: 0,
+            reward_per_share: 0,
+            holder_tiers: Vec::new(),
+            paused: 0,
+            bump: old.bump,
+            authority_bump: old.authority_bump,
+            reserved: [0; RESERVED_SPACE]
+        };
+
+        let pool_info = self.pool.to_account_info();
+        self.resize(&pool_info, 8 + Pool::INIT_SPACE)?;
+        pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+        // Curves are shared, so only the first pool migrated converts its curve
+        if self.curve.data_len() == CurveV0::SPACE {
+            let old = {
+                let data = self.curve.try_borrow_data()?;
+                require!(data[..8] == Curve::DISCRIMINATOR, CurveError::InvalidLegacyAccount);
+                CurveV0::deserialize(&mut &data[8..])?
+            };
+
+            let curve = Curve {
+                version: CURRENT_VERSION,
+                pow: old.pow,
+                frac: old.frac,
+                reserved: [0; RESERVED_SPACE]
+            };
+
+            let curve_info = self.curve.to_account_info();
+            self.resize(&curve_info, 8 + Curve::INIT_SPACE)?;
+            curve.try_serialize(&mut &mut curve_info.try_borrow_mut_data()?[..])?;
+        }
+
+        // Legacy buys priced whole tokens and rounded each one down, so the reserve
+        // can fall short of what the current curve pays out. Selling the whole
+        // supply has to be covered, top the pool up first if it isn't
+        let curve = Curve::try_deserialize(&mut &self.curve.try_borrow_data()?[..])?;
+        let payout = curve.sell_price(self.mint.supply, self.mint.supply, self.mint.decimals)?;
+        let reserve = pool_info.lamports().saturating_sub(Rent::get()?.minimum_balance(pool_info.data_len()));
+        require!(reserve >= payout, CurveError::UnderfundedReserve);
+
+        if self.creator_profile.creator_id.is_empty() {
+            self.creator_profile.version = CURRENT_VERSION;
+            self.creator_profile.creator_id = creator_id;
+            self.creator_profile.last_activity = clock.unix_timestamp;
+            self.creator_profile.bump = bumps.creator_profile;
+        }
+        if self.creator_profile.mint.is_none() {
+            self.creator_profile.mint = Some(pool.mint);
+        }
+
+        if self.observations.pool == Pubkey::default() {
+            let mut observations = [Observation::default(); OBSERVATIONS_LEN];
+            observations[0].timestamp = clock.unix_timestamp;
+
+            self.observations.set_inner(Observations {
+                version: CURRENT_VERSION,
+                pool: self.pool.key(),
+                index: 0,
+                observations,
+                bump: bumps.observations,
+                reserved: [0; RESERVED_SPACE]
+            });
+        }
+
+        Ok(())
+    }
+
+    // Has the admin pay the extra rent before growing the account, so none
+    // of a pool's reserve goes to it
+    fn resize(&self, info: &AccountInfo<'info>, space: usize) -> Result<()> {
+        let rent = Rent::get()?;
+        let shortfall = rent.minimum_balance(space).saturating_sub(rent.minimum_balance(info.data_len()));
+
+        if shortfall > 0 {
+            let accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: info.clone()
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(cpi_ctx, shortfall)?;
+        }
+
+        info.realloc(space, true)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
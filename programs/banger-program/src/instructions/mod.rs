@@ -1,14 +1,134 @@
+pub mod init_config;
+pub use init_config::*;
+
 pub mod init_curve;
 pub use init_curve::*;
 
 pub mod init_pool;
 pub use init_pool::*;
 
+pub mod set_launch_settings;
+pub use set_launch_settings::*;
+
 pub mod buy;
 pub use buy::*;
 
+pub mod buy_basket;
+pub use buy_basket::*;
+
 pub mod sell;
 pub use sell::*;
 
+pub mod swap_creator_tokens;
+pub use swap_creator_tokens::*;
+
+pub mod place_order;
+pub use place_order::*;
+
+pub mod execute_order;
+pub use execute_order::*;
+
+pub mod cancel_order;
+pub use cancel_order::*;
+
+pub mod open_dca;
+pub use open_dca::*;
+
+pub mod execute_dca;
+pub use execute_dca::*;
+
+pub mod close_dca;
+pub use close_dca::*;
+
+pub mod set_staking_share;
+pub use set_staking_share::*;
+
+pub mod stake;
+pub use stake::*;
+
+pub mod unstake;
+pub use unstake::*;
+
+pub mod claim_rewards;
+pub use claim_rewards::*;
+
+pub mod set_holder_tiers;
+pub use set_holder_tiers::*;
+
+pub mod verify_holding;
+pub use verify_holding::*;
+
 pub mod claim;
-pub use claim::*;
\ No newline at end of file
+pub use claim::*;
+
+pub mod tip_creator;
+pub use tip_creator::*;
+
+pub mod claim_tokens;
+pub use claim_tokens::*;
+
+pub mod set_vault_expiry;
+pub use set_vault_expiry::*;
+
+pub mod reclaim_vault;
+pub use reclaim_vault::*;
+
+pub mod pause;
+pub use pause::*;
+
+pub mod propose_admin;
+pub use propose_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod set_roles;
+pub use set_roles::*;
+
+pub mod set_pool_fees;
+pub use set_pool_fees::*;
+
+pub mod set_treasury_split;
+pub use set_treasury_split::*;
+
+pub mod withdraw_treasury;
+pub use withdraw_treasury::*;
+
+pub mod withdraw_treasury_tokens;
+pub use withdraw_treasury_tokens::*;
+
+pub mod set_referral_fee;
+pub use set_referral_fee::*;
+
+pub mod init_referral;
+pub use init_referral::*;
+
+pub mod claim_referral;
+pub use claim_referral::*;
+
+pub mod link_creator;
+pub use link_creator::*;
+
+pub mod claim_pool;
+pub use claim_pool::*;
+
+pub mod close_pool;
+pub use close_pool::*;
+
+pub mod migrate_pool;
+pub use migrate_pool::*;
+
+pub mod reserve_creator_allocation;
+pub use reserve_creator_allocation::*;
+
+pub mod create_vesting;
+pub use create_vesting::*;
+
+pub mod claim_vested;
+pub use claim_vested::*;
+
+pub mod revoke_unvested;
+pub use revoke_unvested::*;
+
+pub mod get_twap;
+pub use get_twap::*;
\ No newline at end of file
@@ -0,0 +1,92 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use crate::state::{Pool, Position, DcaPlan, CURRENT_VERSION, RESERVED_SPACE};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct OpenDca<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DcaPlan::INIT_SPACE,
+        seeds = [b"dca", pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub dca_plan: Box<Account<'info, DcaPlan>>,
+
+    // Created here so the crank doesn't pay for them
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> OpenDca<'info> {
+    pub fn open_dca(
+        &mut self,
+        lamports_per_interval: u64,
+        interval: i64,
+        max_price: u64,
+        deposit: u64,
+        bumps: &OpenDcaBumps
+    ) -> Result<()> {
+        require!(interval > 0, CurveError::InvalidDcaInterval);
+        self.pool.check_sol_quoted()?;
+
+        self.position.init_if_needed(self.owner.key(), self.pool.key(), bumps.position);
+
+        let accounts = Transfer {
+            from: self.owner.to_account_info(),
+            to: self.dca_plan.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        transfer(cpi_ctx, deposit)?;
+
+        self.dca_plan.set_inner(DcaPlan {
+            version: CURRENT_VERSION,
+            owner: self.owner.key(),
+            pool: self.pool.key(),
+            lamports_per_interval,
+            interval,
+            max_price,
+            last_executed: 0,
+            bump: bumps.dca_plan,
+            reserved: [0; RESERVED_SPACE]
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
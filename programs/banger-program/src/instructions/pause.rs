@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Pool, PAUSE_ALL};
+use crate::errors::CurveError;
+
+// Pauses or unpauses the given flags on a single pool when one is passed,
+// otherwise program wide on the config
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = pauser
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Option<Account<'info, Pool>>
+}
+
+impl<'info> SetPause<'info> {
+    pub fn pause(&mut self, flags: u8) -> Result<()> {
+        require!(flags & !PAUSE_ALL == 0, CurveError::InvalidPauseFlags);
+
+        match &mut self.pool {
+            Some(pool) => pool.paused |= flags,
+            None => self.config.paused |= flags
+        }
+
+        Ok(())
+    }
+
+    pub fn unpause(&mut self, flags: u8) -> Result<()> {
+        require!(flags & !PAUSE_ALL == 0, CurveError::InvalidPauseFlags);
+
+        match &mut self.pool {
+            Some(pool) => pool.paused &= !flags,
+            None => self.config.paused &= !flags
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,138 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        self,
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use crate::state::{Pool, Position, Order, OrderSide, CURRENT_VERSION, RESERVED_SPACE};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Order::INIT_SPACE,
+        seeds = [b"order", pool.key().as_ref(), owner.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub order: Box<Account<'info, Order>>,
+
+    // Only for sell orders
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = authority,
+        seeds = [b"order_escrow", order.key().as_ref()],
+        bump
+    )]
+    pub order_escrow: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_ata: Box<Account<'info, TokenAccount>>,
+
+    // Created here so keepers don't pay for it on execution
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// CHECK: custody of the escrow
+    #[account(
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> PlaceOrder<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_order(
+        &mut self,
+        id: u64,
+        side: OrderSide,
+        amount: u64,
+        trigger_price: u64,
+        limit: u64,
+        keeper_tip: u64,
+        bumps: &PlaceOrderBumps
+    ) -> Result<()> {
+        self.pool.check_sol_quoted()?;
+
+        self.position.init_if_needed(self.owner.key(), self.pool.key(), bumps.position);
+
+        // Escrow the keeper tip, plus the spending limit for buys
+        let lamports = match side {
+            OrderSide::Buy => limit.checked_add(keeper_tip).ok_or(CurveError::Overflow)?,
+            OrderSide::Sell => keeper_tip
+        };
+
+        let accounts = Transfer {
+            from: self.owner.to_account_info(),
+            to: self.order.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        transfer(cpi_ctx, lamports)?;
+
+        if side == OrderSide::Buy {
+            require!(self.order_escrow.is_none(), CurveError::UnexpectedOrderEscrow);
+        }
+
+        if side == OrderSide::Sell {
+            let escrow = self.order_escrow.as_ref().ok_or(CurveError::MissingOrderEscrow)?;
+
+            let accounts = token::Transfer {
+                from: self.owner_ata.to_account_info(),
+                to: escrow.to_account_info(),
+                authority: self.owner.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        self.order.set_inner(Order {
+            version: CURRENT_VERSION,
+            owner: self.owner.key(),
+            pool: self.pool.key(),
+            id,
+            side,
+            amount,
+            trigger_price,
+            limit,
+            keeper_tip,
+            bump: bumps.order,
+            reserved: [0; RESERVED_SPACE]
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>
+}
+
+impl<'info> ProposeAdmin<'info> {
+    // Passing `None` cancels an outstanding proposal
+    pub fn propose_admin(&mut self, new_admin: Option<Pubkey>) -> Result<()> {
+        self.config.pending_admin = new_admin;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        self,
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use crate::state::{Config, CreatorProfile, Pool, ReclaimTarget};
+use crate::events::VaultReclaimed;
+use crate::errors::CurveError;
+
+// Permissionless sweep of a creator vault that has been idle past the configured expiry.
+// Lamports are swept every time, one token balance of the vault along with them if given.
+#[derive(Accounts)]
+#[instruction(creator_id: String)]
+pub struct ReclaimVault<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [b"creator", creator_id.as_bytes()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    // Needed when reclaiming to stakers
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump,
+        constraint = creator_profile.mint == Some(pool.mint)
+    )]
+    pub pool: Option<Box<Account<'info, Pool>>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    // Token sweeps only, quote fees or token tips
+    pub mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = creator_vault
+    )]
+    pub vault_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    // When the mint is the pool's quote mint and the target is stakers
+    #[account(mut)]
+    pub quote_reserve: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> ReclaimVault<'info> {
+    pub fn reclaim_vault(&mut self, creator_id: String, bumps: &ReclaimVaultBumps) -> Result<()> {
+        let expiry = self.config.vault_expiry;
+        require!(expiry.period > 0, CurveError::VaultExpiryDisabled);
+
+        let inactive_for = Clock::get()?.unix_timestamp
+            .checked_sub(self.creator_profile.last_activity).ok_or(CurveError::Overflow)?;
+        require!(inactive_for >= expiry.period, CurveError::VaultNotExpired);
+
+        // Leaving the pool out must not divert the stakers' share to the treasury
+        if expiry.target == ReclaimTarget::Stakers && self.creator_profile.mint.is_some() {
+            require!(self.pool.is_some(), CurveError::MissingReclaimAccounts);
+        }
+
+        // The vault stays rent exempt to keep receiving fees
+        let amount = self.creator_vault.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+
+        // Staking rewards on quote token pools are paid in the quote token
+        let lamports_to_stakers = match (&mut self.pool, expiry.target) {
+            (Some(pool), ReclaimTarget::Stakers) if pool.quote_mint.is_none() => pool.distribute_rewards(amount)?,
+            _ => false
+        };
+
+        let to = match (&self.pool, lamports_to_stakers) {
+            (Some(pool), true) => pool.to_account_info(),
+            _ => self.treasury.to_account_info()
+        };
+
+        let seeds = &[
+            &b"creator_vault"[..],
+            creator_id.as_bytes(),
+            &[bumps.creator_vault]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: self.creator_vault.to_account_info(),
+            to
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), accounts, signer_seeds);
+        transfer(cpi_ctx, amount)?;
+
+        let (mint, token_amount, tokens_to_stakers) = match self.mint.as_ref().map(|m| m.key()) {
+            Some(mint) => {
+                let (token_amount, to_stakers) = self.reclaim_tokens(mint, expiry.target, signer_seeds)?;
+                (Some(mint), token_amount, to_stakers)
+            },
+            None => (None, 0, false)
+        };
+
+        emit!(VaultReclaimed {
+            creator_id,
+            amount,
+            to_stakers: lamports_to_stakers || tokens_to_stakers,
+            inactive_for,
+            mint,
+            token_amount
+        });
+
+        Ok(())
+    }
+
+    fn reclaim_tokens(&mut self, mint: Pubkey, target: ReclaimTarget, signer_seeds: &[&[&[u8]]]) -> Result<(u64, bool)> {
+        let (Some(vault_ata), Some(treasury_ata)) = (&self.vault_ata, &self.treasury_ata) else {
+            return err!(CurveError::MissingReclaimAccounts);
+        };
+        let amount = vault_ata.amount;
+
+        let to_stakers = match (&mut self.pool, target) {
+            (Some(pool), ReclaimTarget::Stakers) if pool.quote_mint == Some(mint) => pool.distribute_rewards(amount)?,
+            _ => false
+        };
+
+        let to = match (&self.pool, to_stakers) {
+            (Some(pool), true) => {
+                let quote_reserve = self.quote_reserve.as_ref().ok_or(CurveError::MissingReclaimAccounts)?;
+                let (address, _) = Pubkey::find_program_address(
+                    &[b"quote_reserve", pool.key().as_ref()],
+                    &crate::ID
+                );
+                require_keys_eq!(quote_reserve.key(), address, CurveError::MissingReclaimAccounts);
+                quote_reserve.to_account_info()
+            },
+            _ => treasury_ata.to_account_info()
+        };
+
+        let accounts = token::Transfer {
+            from: vault_ata.to_account_info(),
+            to,
+            authority: self.creator_vault.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+
+        Ok((amount, to_stakers))
+    }
+}
\ No newline at end of file
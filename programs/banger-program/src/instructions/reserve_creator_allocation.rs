@@ -0,0 +1,344 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    metadata::Metadata,
+    associated_token::AssociatedToken,
+    token::{
+        self,
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use mpl_token_metadata::instructions::{
+        MintV1Cpi, MintV1CpiAccounts, MintV1InstructionArgs
+    };
+pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use crate::state::{Pool, Curve, Config, CreatorProfile, Observations, Position, VestingSchedule, MAX_CREATOR_ALLOCATION, PAUSE_BUY, CURRENT_VERSION, RESERVED_SPACE};
+use crate::errors::CurveError;
+
+// Lets the creator buy an allocation at curve price before anyone else trades, sent
+// in the same transaction as `launch_creator_pool`. A zero `vesting_duration` mints it
+// straight to the creator's wallet, otherwise it vests linearly from an escrow.
+// Recorded like a buy, except for the launch limits: it comes before trading starts
+// and is capped by `MAX_CREATOR_ALLOCATION` instead. Escrowed tokens aren't in the
+// creator's wallet, so only the spend goes on the creator's position for those.
+#[derive(Accounts)]
+pub struct ReserveCreatorAllocation<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"creator", pool.creator_id.as_bytes()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == Some(creator.key()) @ CurveError::NotCreator
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    // Only for vested allocations
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [b"vesting", mint.key().as_ref(), creator.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub vesting: Option<Box<Account<'info, VestingSchedule>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = authority,
+        seeds = [b"vesting_escrow", vesting.as_ref().ok_or(CurveError::AllocationAccountsMismatch)?.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Only for liquid allocations
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator
+    )]
+    pub creator_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: used for signing
+    #[account(
+        mut,
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: will be checked by metaplex
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub curve: Box<Account<'info, Curve>>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", pool.creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = curve,
+        has_one = treasury
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", pool.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    // Quote token pools only
+    #[account(
+        constraint = pool.quote_mint == Some(quote_mint.key()) @ CurveError::MissingQuoteAccounts
+    )]
+    pub quote_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = creator
+    )]
+    pub creator_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_reserve: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator_vault
+    )]
+    pub creator_vault_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(address = INSTRUCTIONS_ID)]
+    /// CHECK: no need to check
+    pub sysvar_instructions: UncheckedAccount<'info>
+}
+
+impl<'info> ReserveCreatorAllocation<'info> {
+    pub fn reserve_creator_allocation(
+        &mut self,
+        amount: u64,
+        amount_in: u64,
+        vesting_duration: i64,
+        bumps: &ReserveCreatorAllocationBumps
+    ) -> Result<()> {
+        self.pool.check_not_paused(&self.config, PAUSE_BUY)?;
+
+        // Supply is back to 0 once every holder sold out, only a pool that never traded is at launch
+        require!(self.mint.supply == 0 && self.pool.stats.trade_count == 0, CurveError::NotAtLaunch);
+        require!(vesting_duration >= 0, CurveError::InvalidVestingDuration);
+
+        let vested = vesting_duration > 0;
+        require!(
+            self.vesting.is_some() == vested && self.vesting_escrow.is_some() == vested && self.creator_ata.is_some() != vested,
+            CurveError::AllocationAccountsMismatch
+        );
+
+        let max_allocation = MAX_CREATOR_ALLOCATION
+            .checked_mul(10u64.pow(self.mint.decimals as u32)).ok_or(CurveError::Overflow)?;
+        require!(amount <= max_allocation, CurveError::AllocationTooLarge);
+
+        let lamports = self.curve.buy_price(0, amount, self.mint.decimals)?;
+        let quote_decimals = self.quote_mint.as_ref().map(|m| m.decimals);
+        let subtotal = self.pool.to_quote(lamports, quote_decimals, true)?;
+        let (creator_fee, banger_fee) = self.pool.fees(subtotal)?;
+
+        let total = subtotal
+            .checked_add(banger_fee).ok_or(CurveError::Overflow)?
+            .checked_add(creator_fee).ok_or(CurveError::Overflow)?;
+        require!(total <= amount_in, CurveError::Slippage);
+
+        let staking_reward = self.pool.record_fees(creator_fee, banger_fee)?;
+        let creator_fee = creator_fee - staking_reward;
+
+        self.position.init_if_needed(self.creator.key(), self.pool.key(), bumps.position);
+        if vested {
+            self.position.spent = self.position.spent.checked_add(total).ok_or(CurveError::Overflow)?;
+        } else {
+            self.pool.stats.holders = self.pool.stats.holders.checked_add(1).ok_or(CurveError::Overflow)?;
+            self.position.record_buy(amount, total, self.mint.decimals)?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let price = self.curve.spot_price(0, self.mint.decimals)?;
+        let price = self.pool.to_quote(price, quote_decimals, false)?;
+        self.observations.record(now, price, subtotal)?;
+        self.pool.stats.record_trade(now, subtotal)?;
+
+        match self.pool.quote_mint {
+            Some(_) => self.pay_quote(subtotal + staking_reward, creator_fee, banger_fee)?,
+            None => self.pay_sol(subtotal + staking_reward, creator_fee, banger_fee)?
+        }
+
+        let seeds = &[
+            &b"authority"[..],
+            &[self.pool.authority_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Into the escrow for vested allocations, the creator's wallet otherwise
+        let (token, token_owner) = match (&self.vesting_escrow, &self.creator_ata) {
+            (Some(escrow), _) => (escrow.to_account_info(), self.authority.to_account_info()),
+            (None, Some(ata)) => (ata.to_account_info(), self.creator.to_account_info()),
+            (None, None) => return err!(CurveError::AllocationAccountsMismatch)
+        };
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let metadata = &self.metadata.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let authority = &self.authority.to_account_info();
+        let payer = &self.creator.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let spl_token_program = &self.token_program.to_account_info();
+        let spl_ata_program = &self.associated_token_program.to_account_info();
+
+        // Mint the allocation
+        let mint_tokens = MintV1Cpi::new(
+            metadata_program,
+            MintV1CpiAccounts {
+                token: &token,
+                token_owner: Some(&token_owner),
+                metadata,
+                master_edition: None,
+                token_record: None,
+                mint,
+                authority,
+                delegate_record: None,
+                payer,
+                system_program,
+                sysvar_instructions,
+                spl_token_program,
+                spl_ata_program,
+                authorization_rules_program: None,
+                authorization_rules: None
+            },
+            MintV1InstructionArgs {
+                amount,
+                authorization_data: None
+            }
+        );
+        mint_tokens.invoke_signed(signer_seeds)?;
+
+        if let Some(vesting) = &mut self.vesting {
+            vesting.set_inner(VestingSchedule {
+                version: CURRENT_VERSION,
+                beneficiary: self.creator.key(),
+                funder: self.creator.key(),
+                mint: self.mint.key(),
+                total: amount,
+                claimed: 0,
+                start: now,
+                cliff: now,
+                duration: vesting_duration,
+                revoked: false,
+                bump: bumps.vesting,
+                reserved: [0; RESERVED_SPACE]
+            });
+        }
+
+        Ok(())
+    }
+
+    fn pay_sol(&self, reserve: u64, creator_fee: u64, banger_fee: u64) -> Result<()> {
+        // Subtotal and stakers' share of the creator fee to the pool, then the fees
+        let payments = [
+            (self.pool.to_account_info(), reserve),
+            (self.creator_vault.to_account_info(), creator_fee),
+            (self.treasury.to_account_info(), banger_fee)
+        ];
+
+        for (to, amount) in payments {
+            let accounts = Transfer {
+                from: self.creator.to_account_info(),
+                to
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(cpi_ctx, amount)?;
+        }
+
+        Ok(())
+    }
+
+    fn pay_quote(&self, reserve: u64, creator_fee: u64, banger_fee: u64) -> Result<()> {
+        let (Some(_), Some(creator_quote_ata), Some(quote_reserve), Some(treasury_quote_ata), Some(creator_vault_quote_ata)) = (
+            &self.quote_mint,
+            &self.creator_quote_ata,
+            &self.quote_reserve,
+            &self.treasury_quote_ata,
+            &self.creator_vault_quote_ata
+        ) else {
+            return err!(CurveError::MissingQuoteAccounts);
+        };
+
+        let payments = [
+            (quote_reserve, reserve),
+            (creator_vault_quote_ata, creator_fee),
+            (treasury_quote_ata, banger_fee)
+        ];
+
+        for (to, amount) in payments {
+            let accounts = token::Transfer {
+                from: creator_quote_ata.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.creator.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    transfer,
+    Mint,
+    Token,
+    TokenAccount,
+    Transfer
+};
+use crate::state::{Config, VestingSchedule};
+use crate::errors::CurveError;
+
+// Returns whatever has not vested yet to the funder; the beneficiary keeps
+// the vested part and can still claim it. Self-funded schedules, like the
+// creator allocation, are lockups and can't be revoked.
+#[derive(Accounts)]
+pub struct RevokeUnvested<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", mint.key().as_ref(), vesting.beneficiary.as_ref(), vesting.funder.as_ref()],
+        bump = vesting.bump,
+        has_one = mint
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting_escrow", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vesting.funder
+    )]
+    pub funder_ata: Account<'info, TokenAccount>,
+
+    /// CHECK: used for signing
+    #[account(
+        seeds = [b"authority"],
+        bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>
+}
+
+impl<'info> RevokeUnvested<'info> {
+    pub fn revoke_unvested(&mut self, bumps: &RevokeUnvestedBumps) -> Result<()> {
+        require!(!self.vesting.revoked, CurveError::VestingRevoked);
+        require_keys_neq!(self.vesting.funder, self.vesting.beneficiary, CurveError::VestingNotRevocable);
+
+        let vested = self.vesting.vested_amount(Clock::get()?.unix_timestamp)?;
+        let unvested = self.vesting.total.checked_sub(vested).ok_or(CurveError::Overflow)?;
+
+        self.vesting.total = vested;
+        self.vesting.revoked = true;
+
+        let seeds = &[
+            &b"authority"[..],
+            &[bumps.authority]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: self.vesting_escrow.to_account_info(),
+            to: self.funder_ata.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        transfer(cpi_ctx, unvested)
+    }
+}
\ No newline at end of file
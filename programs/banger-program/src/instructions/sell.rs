@@ -3,6 +3,7 @@ use anchor_spl::{
     metadata::Metadata,
     associated_token::AssociatedToken,
     token::{
+        self,
         Mint,
         Token,
         TokenAccount
@@ -11,7 +12,7 @@ use mpl_token_metadata::instructions::{
         BurnV1Cpi, BurnV1CpiAccounts, BurnV1InstructionArgs
     };
 pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
-use crate::state::{Pool, Curve};
+use crate::state::{Pool, Curve, Config, Position, Observations, Referral, PAUSE_SELL};
 use crate::errors::CurveError;
 
 #[derive(Accounts)]
@@ -63,6 +64,72 @@ pub struct Sell<'info> {
     )]
     pub pool: Account<'info, Pool>,
 
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", pool.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool.key().as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.referrer.as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    // Quote token pools only
+    #[account(
+        constraint = pool.quote_mint == Some(quote_mint.key()) @ CurveError::MissingQuoteAccounts
+    )]
+    pub quote_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = quote_mint,
+        associated_token::authority = seller
+    )]
+    pub seller_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_reserve: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = quote_mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator_vault
+    )]
+    pub creator_vault_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
     pub system_program: Program<'info, System>,
     pub metadata_program: Program<'info, Metadata>,
     pub token_program: Program<'info, Token>,
@@ -74,52 +141,43 @@ pub struct Sell<'info> {
 }
 
 impl<'info> Sell<'info> {
-    pub fn sell(&mut self, num_burn: u64, amount_out: u64) -> Result<()> {
+    pub fn sell(&mut self, num_burn: u64, amount_out: u64, bumps: &SellBumps) -> Result<()> {
 
-        let current_supply = self.mint.supply;
+        self.pool.check_not_paused(&self.config, PAUSE_SELL)?;
 
-        let mut total: u64 = 0;
+        let current_supply = self.mint.supply;
 
-        for i in 0..num_burn {
-            let supply = current_supply - (i + 1);
-            let price = supply
-                .checked_pow(self.curve.pow as u32).ok_or(CurveError::Overflow)?
-                .checked_div(self.curve.frac).ok_or(CurveError::Overflow)?;
+        let lamports = self.curve.sell_price(current_supply, num_burn, self.mint.decimals)?;
+        let quote_decimals = self.quote_mint.as_ref().map(|m| m.decimals);
+        let total = self.pool.to_quote(lamports, quote_decimals, false)?;
 
-            total = total.checked_add(price).ok_or(CurveError::Overflow)?;
-        }
+        let (creator_fee, banger_fee) = self.pool.fees(total)?;
 
-        total = total.checked_mul(1_000_000_000).ok_or(CurveError::Overflow)?;
+        let subtotal = total
+            .checked_sub(banger_fee).ok_or(CurveError::Overflow)?
+            .checked_sub(creator_fee).ok_or(CurveError::Overflow)?;
 
-        let banger_fee = total
-            .checked_mul(self.pool.banger_fee as u64).ok_or(CurveError::Overflow)?
-            .checked_div(10000).ok_or(CurveError::Overflow)?;
+        msg!("supply: {}, num_burn: {}, total: {}, subtotal: {}", current_supply, num_burn, total, subtotal);
+        require!(subtotal >= amount_out, CurveError::Slippage);
 
-        let creator_fee = total
-            .checked_mul(self.pool.creator_fee as u64).ok_or(CurveError::Overflow)?
-            .checked_div(10000).ok_or(CurveError::Overflow)?;
+        let staking_reward = self.pool.record_fees(creator_fee, banger_fee)?;
+        let creator_fee = creator_fee - staking_reward;
 
-        let subtotal = total
-            .checked_sub(banger_fee).ok_or(CurveError::Overflow)?
-            .checked_sub(banger_fee).ok_or(CurveError::Overflow)?;
-        let amount_out_str = amount_out.to_string();
-        let amount_out_str_ref = amount_out_str.as_str();
-        let total_str = total.to_string();
-        let total_str_ref = total_str.as_ref();
-        msg!(amount_out_str_ref);
-        msg!(total_str_ref);
-        require!(amount_out >= total, CurveError::Slippage);
+        self.position.init_if_needed(self.seller.key(), self.pool.key(), bumps.position);
+        let was_holder = self.position.balance > 0;
+        self.position.record_sell(num_burn, subtotal, self.mint.decimals)?;
+        if was_holder && self.position.balance == 0 {
+            self.pool.stats.holders = self.pool.stats.holders.saturating_sub(1);
+        }
 
-        **self.pool.to_account_info().try_borrow_mut_lamports()? -= subtotal;
-        **self.seller.to_account_info().try_borrow_mut_lamports()? += subtotal;
-        
-        **self.pool.to_account_info().try_borrow_mut_lamports()? -= creator_fee;
-        **self.creator_vault.to_account_info().try_borrow_mut_lamports()? += creator_fee;
-        
-        **self.pool.to_account_info().try_borrow_mut_lamports()? -= banger_fee;
-        **self.treasury.to_account_info().try_borrow_mut_lamports()? += banger_fee;
+        let now = Clock::get()?.unix_timestamp;
+        let price = self.curve.spot_price(current_supply, self.mint.decimals)?;
+        let price = self.pool.to_quote(price, quote_decimals, false)?;
+        self.observations.record(now, price, total)?;
+        self.pool.stats.record_trade(now, total)?;
 
         // Burn tokens from seller
+        let metadata_program = &self.metadata_program.to_account_info();
         let metadata = &self.metadata.to_account_info();
         let mint = &self.mint.to_account_info();
         let seller = &self.seller.to_account_info();
@@ -129,7 +187,7 @@ impl<'info> Sell<'info> {
         let spl_token_program = &self.token_program.to_account_info();
         
         let burn_tokens = BurnV1Cpi::new(
-            spl_token_program,
+            metadata_program,
             BurnV1CpiAccounts {
                 authority: seller,
                 collection_metadata: None,
@@ -153,6 +211,82 @@ impl<'info> Sell<'info> {
         burn_tokens.invoke()?;
         msg!("Tokens burned!");
 
+        // Pay out after the burn so no lamports move ahead of the CPI
+        match self.pool.quote_mint {
+            Some(_) => self.pay_quote(subtotal, creator_fee, banger_fee)?,
+            None => self.pay_sol(subtotal, creator_fee, banger_fee)?
+        }
+
+        Ok(())
+    }
+
+    fn pay_sol(&mut self, subtotal: u64, creator_fee: u64, banger_fee: u64) -> Result<()> {
+        **self.pool.to_account_info().try_borrow_mut_lamports()? -= subtotal;
+        **self.seller.to_account_info().try_borrow_mut_lamports()? += subtotal;
+        
+        **self.pool.to_account_info().try_borrow_mut_lamports()? -= creator_fee;
+        **self.creator_vault.to_account_info().try_borrow_mut_lamports()? += creator_fee;
+        
+        let (treasury_fee, referral_fee) = match &self.referral {
+            Some(referral) => {
+                require_keys_neq!(referral.referrer, self.seller.key(), CurveError::SelfReferral);
+                self.config.split_referral_fee(banger_fee)?
+            },
+            None => (banger_fee, 0)
+        };
+
+        **self.pool.to_account_info().try_borrow_mut_lamports()? -= treasury_fee;
+        **self.treasury.to_account_info().try_borrow_mut_lamports()? += treasury_fee;
+
+        if let Some(referral) = &mut self.referral {
+            **self.pool.to_account_info().try_borrow_mut_lamports()? -= referral_fee;
+            **referral.to_account_info().try_borrow_mut_lamports()? += referral_fee;
+            referral.accrue(referral_fee)?;
+            self.pool.record_referral_fee(referral_fee)?;
+        }
+
+        Ok(())
+    }
+
+    // Referrals are only paid on SOL pools
+    fn pay_quote(&self, subtotal: u64, creator_fee: u64, banger_fee: u64) -> Result<()> {
+        require!(self.referral.is_none(), CurveError::QuotePoolUnsupported);
+
+        let (Some(_), Some(seller_quote_ata), Some(quote_reserve), Some(treasury_quote_ata), Some(creator_vault_quote_ata)) = (
+            &self.quote_mint,
+            &self.seller_quote_ata,
+            &self.quote_reserve,
+            &self.treasury_quote_ata,
+            &self.creator_vault_quote_ata
+        ) else {
+            return err!(CurveError::MissingQuoteAccounts);
+        };
+
+        let mint_key = self.mint.key();
+        let seeds = &[
+            &b"pool"[..],
+            mint_key.as_ref(),
+            &[self.pool.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Stakers' share of the creator fee stays in the reserve
+        let payments = [
+            (seller_quote_ata, subtotal),
+            (creator_vault_quote_ata, creator_fee),
+            (treasury_quote_ata, banger_fee)
+        ];
+
+        for (to, amount) in payments {
+            let accounts = token::Transfer {
+                from: quote_reserve.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.pool.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, CreatorProfile, MAX_HOLDER_TIERS};
+use crate::errors::CurveError;
+
+// Tiers are the creator's perks to hand out, so they're set by the linked wallet
+#[derive(Accounts)]
+pub struct SetHolderTiers<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"creator", pool.creator_id.as_bytes()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == Some(creator.key()) @ CurveError::NotCreator
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>
+}
+
+impl<'info> SetHolderTiers<'info> {
+    // Thresholds are in base units, tier n is reached by holding at least tiers[n-1]
+    pub fn set_holder_tiers(&mut self, tiers: Vec<u64>) -> Result<()> {
+        require!(tiers.len() <= MAX_HOLDER_TIERS, CurveError::InvalidHolderTiers);
+        require!(
+            tiers.windows(2).all(|w| w[0] < w[1]),
+            CurveError::InvalidHolderTiers
+        );
+
+        self.pool.holder_tiers = tiers;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{Pool, CreatorProfile, LaunchSettings};
+use crate::errors::CurveError;
+
+// Settings given at creation can be changed by the linked creator until the first buy
+#[derive(Accounts)]
+pub struct SetLaunchSettings<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"creator", pool.creator_id.as_bytes()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == Some(creator.key()) @ CurveError::NotCreator
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = mint
+    )]
+    pub pool: Account<'info, Pool>
+}
+
+impl<'info> SetLaunchSettings<'info> {
+    pub fn set_launch_settings(&mut self, launch: LaunchSettings) -> Result<()> {
+        require!(self.pool.stats.trade_count == 0, CurveError::AlreadyTrading);
+
+        self.pool.launch = launch;
+
+        Ok(())
+    }
+}
\ No newline at end of file
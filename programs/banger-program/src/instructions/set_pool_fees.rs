@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Pool};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct SetPoolFees<'info> {
+    pub fee_manager: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = fee_manager
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>
+}
+
+impl<'info> SetPoolFees<'info> {
+    pub fn set_pool_fees(&mut self, creator_fee: u16, banger_fee: u16) -> Result<()> {
+        require!(
+            (creator_fee as u32) + (banger_fee as u32) <= 10000,
+            CurveError::InvalidFee
+        );
+
+        self.pool.creator_fee = creator_fee;
+        self.pool.banger_fee = banger_fee;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct SetReferralFee<'info> {
+    pub fee_manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = fee_manager
+    )]
+    pub config: Account<'info, Config>
+}
+
+impl<'info> SetReferralFee<'info> {
+    pub fn set_referral_fee(&mut self, referral_fee: u16) -> Result<()> {
+        require!(referral_fee <= 10000, CurveError::InvalidFee);
+
+        self.config.referral_fee = referral_fee;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetRoles<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>
+}
+
+impl<'info> SetRoles<'info> {
+    pub fn set_roles(
+        &mut self,
+        fee_manager: Pubkey,
+        pauser: Pubkey,
+        creator_verifier: Pubkey
+    ) -> Result<()> {
+        self.config.fee_manager = fee_manager;
+        self.config.pauser = pauser;
+        self.config.creator_verifier = creator_verifier;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, CreatorProfile};
+use crate::errors::CurveError;
+
+// The creator decides how much of their fee goes to stakers. Whoever launched the pool
+// doesn't count, only the wallet linked to its creator id.
+#[derive(Accounts)]
+pub struct SetStakingShare<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"creator", pool.creator_id.as_bytes()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == Some(creator.key()) @ CurveError::NotCreator
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>
+}
+
+impl<'info> SetStakingShare<'info> {
+    pub fn set_staking_share(&mut self, staking_share: u16) -> Result<()> {
+        require!(staking_share <= 10000, CurveError::InvalidFee);
+
+        self.pool.staking_share = staking_share;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, TreasuryShare, MAX_TREASURY_RECIPIENTS};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct SetTreasurySplit<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>
+}
+
+impl<'info> SetTreasurySplit<'info> {
+    // An empty split sends withdrawals to the admin
+    pub fn set_treasury_split(&mut self, split: Vec<TreasuryShare>) -> Result<()> {
+        require!(split.len() <= MAX_TREASURY_RECIPIENTS, CurveError::InvalidTreasurySplit);
+
+        if !split.is_empty() {
+            let total = split.iter().map(|s| s.share as u32).sum::<u32>();
+            require!(total == 10000, CurveError::InvalidTreasurySplit);
+        }
+
+        self.config.treasury_split = split;
+
+        Ok(())
+    }
+}
\ No newline at end of file
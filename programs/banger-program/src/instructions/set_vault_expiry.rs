@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, VaultExpiry};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct SetVaultExpiry<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>
+}
+
+impl<'info> SetVaultExpiry<'info> {
+    pub fn set_vault_expiry(&mut self, vault_expiry: VaultExpiry) -> Result<()> {
+        require!(vault_expiry.period >= 0, CurveError::InvalidVaultExpiry);
+
+        self.config.vault_expiry = vault_expiry;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        transfer,
+        Mint,
+        Token,
+        TokenAccount,
+        Transfer
+    }};
+use crate::state::{Pool, Stake, CURRENT_VERSION};
+use crate::errors::CurveError;
+
+#[derive(Accounts)]
+pub struct StakeTokens<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = mint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Stake::INIT_SPACE,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub stake: Box<Account<'info, Stake>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"stake_vault", pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = authority
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: owns the stake vault
+    #[account(
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> StakeTokens<'info> {
+    pub fn stake(&mut self, amount: u64, bumps: &StakeTokensBumps) -> Result<()> {
+        if self.stake.owner == Pubkey::default() {
+            self.stake.version = CURRENT_VERSION;
+            self.stake.owner = self.owner.key();
+            self.stake.pool = self.pool.key();
+            self.stake.bump = bumps.stake;
+            self.pool.stakers = self.pool.stakers.checked_add(1).ok_or(CurveError::Overflow)?;
+        }
+
+        let staked = self.stake.amount.checked_add(amount).ok_or(CurveError::Overflow)?;
+        self.stake.set_amount(staked, self.pool.reward_per_share)?;
+        self.pool.total_staked = self.pool.total_staked.checked_add(amount).ok_or(CurveError::Overflow)?;
+
+        // Adding to a stake locks all of it again
+        self.stake.staked_at = Clock::get()?.unix_timestamp;
+
+        let accounts = Transfer {
+            from: self.owner_ata.to_account_info(),
+            to: self.stake_vault.to_account_info(),
+            authority: self.owner.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+}
\ No newline at end of file
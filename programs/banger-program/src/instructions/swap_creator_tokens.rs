@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::Metadata,
+    associated_token::AssociatedToken,
+    token::{
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use mpl_token_metadata::instructions::{
+        BurnV1Cpi, BurnV1CpiAccounts, BurnV1InstructionArgs,
+        MintV1Cpi, MintV1CpiAccounts, MintV1InstructionArgs
+    };
+pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use crate::state::{Pool, Curve, Config, Position, Observations, PAUSE_BUY, PAUSE_SELL};
+use crate::errors::CurveError;
+
+// Sells along the curve of pool A and buys along the curve of pool B with the
+// proceeds. Fees are charged once, at pool A's rates, with the creator fee split
+// evenly between both creators and their stakers. Any lamports that can't buy a
+// whole base unit of B are refunded.
+#[derive(Accounts)]
+pub struct SwapCreatorTokens<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    #[account(mut)]
+    pub mint_a: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = trader
+    )]
+    pub trader_ata_a: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: will be checked by metaplex
+    #[account(mut)]
+    pub metadata_a: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", pool_a.creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault_a: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint_a.key().as_ref()],
+        bump = pool_a.bump,
+        has_one = curve,
+        has_one = treasury
+    )]
+    pub pool_a: Box<Account<'info, Pool>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", pool_a.key().as_ref(), trader.key().as_ref()],
+        bump
+    )]
+    pub position_a: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool_a.key().as_ref()],
+        bump = observations_a.bump
+    )]
+    pub observations_a: Box<Account<'info, Observations>>,
+
+    #[account(mut)]
+    pub mint_b: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        associated_token::mint = mint_b,
+        associated_token::authority = trader
+    )]
+    pub trader_ata_b: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: will be checked by metaplex
+    #[account(mut)]
+    pub metadata_b: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint_b.key().as_ref()],
+        bump = pool_b.bump,
+        has_one = curve,
+        has_one = treasury
+    )]
+    pub pool_b: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", pool_b.creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault_b: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", pool_b.key().as_ref(), trader.key().as_ref()],
+        bump
+    )]
+    pub position_b: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", pool_b.key().as_ref()],
+        bump = observations_b.bump
+    )]
+    pub observations_b: Box<Account<'info, Observations>>,
+
+    /// CHECK: used for signing
+    #[account(
+        mut,
+        seeds = [b"authority"],
+        bump = pool_b.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    pub curve: Box<Account<'info, Curve>>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub system_program: Program<'info, System>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(address = INSTRUCTIONS_ID)]
+    /// CHECK: no need to check
+    pub sysvar_instructions: UncheckedAccount<'info>
+}
+
+impl<'info> SwapCreatorTokens<'info> {
+    pub fn swap_creator_tokens(
+        &mut self,
+        num_burn: u64,
+        min_out: u64,
+        bumps: &SwapCreatorTokensBumps
+    ) -> Result<()> {
+        require_keys_neq!(self.pool_a.key(), self.pool_b.key(), CurveError::SwapSamePool);
+
+        self.pool_a.check_not_paused(&self.config, PAUSE_SELL)?;
+        self.pool_b.check_not_paused(&self.config, PAUSE_BUY)?;
+        self.pool_a.check_sol_quoted()?;
+        self.pool_b.check_sol_quoted()?;
+
+        let supply_a = self.mint_a.supply;
+        let supply_b = self.mint_b.supply;
+
+        // Leg A: sell along the curve, fees come out of the proceeds
+        let gross = self.curve.sell_price(supply_a, num_burn, self.mint_a.decimals)?;
+        let (creator_fee, banger_fee) = self.pool_a.fees(gross)?;
+
+        let net = gross
+            .checked_sub(banger_fee).ok_or(CurveError::Overflow)?
+            .checked_sub(creator_fee).ok_or(CurveError::Overflow)?;
+
+        // Leg B: buy as much as the proceeds cover, without fees
+        let (num_mint, cost) = self.curve.max_buy(supply_b, net, self.mint_b.decimals)?;
+        let refund = net - cost;
+
+        msg!("num_burn: {}, gross: {}, num_mint: {}, cost: {}, refund: {}", num_burn, gross, num_mint, cost, refund);
+        require!(num_mint > 0, CurveError::NothingToBuy);
+        require!(num_mint >= min_out, CurveError::Slippage);
+
+        self.position_b.init_if_needed(self.trader.key(), self.pool_b.key(), bumps.position_b);
+        let clock = Clock::get()?;
+        self.pool_b.check_launch_limits(&mut self.position_b, num_mint, &clock)?;
+
+        let creator_fee_b = creator_fee / 2;
+        let creator_fee_a = creator_fee - creator_fee_b;
+        let staking_reward_a = self.pool_a.record_fees(creator_fee_a, banger_fee)?;
+        let staking_reward_b = self.pool_b.record_fees(creator_fee_b, 0)?;
+        let creator_fee_a = creator_fee_a - staking_reward_a;
+        let creator_fee_b = creator_fee_b - staking_reward_b;
+
+        self.position_a.init_if_needed(self.trader.key(), self.pool_a.key(), bumps.position_a);
+        let was_holder = self.position_a.balance > 0;
+        self.position_a.record_sell(num_burn, net, self.mint_a.decimals)?;
+        if was_holder && self.position_a.balance == 0 {
+            self.pool_a.stats.holders = self.pool_a.stats.holders.saturating_sub(1);
+        }
+
+        if self.position_b.balance == 0 {
+            self.pool_b.stats.holders = self.pool_b.stats.holders.checked_add(1).ok_or(CurveError::Overflow)?;
+        }
+        self.position_b.record_buy(num_mint, cost, self.mint_b.decimals)?;
+
+        let price_a = self.curve.spot_price(supply_a, self.mint_a.decimals)?;
+        self.observations_a.record(clock.unix_timestamp, price_a, gross)?;
+        self.pool_a.stats.record_trade(clock.unix_timestamp, gross)?;
+
+        let price_b = self.curve.spot_price(supply_b, self.mint_b.decimals)?;
+        self.observations_b.record(clock.unix_timestamp, price_b, cost)?;
+        self.pool_b.stats.record_trade(clock.unix_timestamp, cost)?;
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let trader = &self.trader.to_account_info();
+        let authority = &self.authority.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let spl_token_program = &self.token_program.to_account_info();
+        let spl_ata_program = &self.associated_token_program.to_account_info();
+
+        let metadata_a = &self.metadata_a.to_account_info();
+        let mint_a = &self.mint_a.to_account_info();
+        let token_a = &self.trader_ata_a.to_account_info();
+
+        let metadata_b = &self.metadata_b.to_account_info();
+        let mint_b = &self.mint_b.to_account_info();
+        let token_b = &self.trader_ata_b.to_account_info();
+
+        // Burn A from trader
+        let burn_tokens = BurnV1Cpi::new(
+            metadata_program,
+            BurnV1CpiAccounts {
+                authority: trader,
+                collection_metadata: None,
+                metadata: metadata_a,
+                edition: None,
+                mint: mint_a,
+                token: token_a,
+                master_edition: None,
+                master_edition_mint: None,
+                master_edition_token: None,
+                edition_marker: None,
+                token_record: None,
+                system_program,
+                sysvar_instructions,
+                spl_token_program,
+            },
+            BurnV1InstructionArgs {
+                amount: num_burn
+            }
+        );
+        burn_tokens.invoke()?;
+
+        let seeds = &[
+            &b"authority"[..],
+            &[self.pool_b.authority_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Mint B to trader
+        let mint_tokens = MintV1Cpi::new(
+            metadata_program,
+            MintV1CpiAccounts {
+                token: token_b,
+                token_owner: Some(trader),
+                metadata: metadata_b,
+                master_edition: None,
+                token_record: None,
+                mint: mint_b,
+                authority,
+                delegate_record: None,
+                payer: trader,
+                system_program,
+                sysvar_instructions,
+                spl_token_program,
+                spl_ata_program,
+                authorization_rules_program: None,
+                authorization_rules: None
+            },
+            MintV1InstructionArgs {
+                amount: num_mint,
+                authorization_data: None
+            }
+        );
+        mint_tokens.invoke_signed(signer_seeds)?;
+
+        // Move the proceeds out of pool A's reserve, after the CPIs so no lamports move ahead of them
+        **self.pool_a.to_account_info().try_borrow_mut_lamports()? -= gross - staking_reward_a;
+        **self.creator_vault_a.to_account_info().try_borrow_mut_lamports()? += creator_fee_a;
+        **self.creator_vault_b.to_account_info().try_borrow_mut_lamports()? += creator_fee_b;
+        **self.treasury.to_account_info().try_borrow_mut_lamports()? += banger_fee;
+        **self.pool_b.to_account_info().try_borrow_mut_lamports()? += cost + staking_reward_b;
+        **self.trader.to_account_info().try_borrow_mut_lamports()? += refund;
+        msg!("Tokens swapped!");
+
+        Ok(())
+    }
+}
\ No newline at end of file
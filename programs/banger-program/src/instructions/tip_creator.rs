@@ -0,0 +1,130 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        self,
+        Mint,
+        Token,
+        TokenAccount
+    }};
+use crate::state::{CreatorProfile, CURRENT_VERSION, MAX_CREATOR_ID_LEN, MAX_TIP_MEMO_LEN};
+use crate::events::CreatorTipped;
+use crate::errors::CurveError;
+
+// Tips land in the creator vault, or its ATA for token tips, and are claimable
+// once the creator links a wallet
+#[derive(Accounts)]
+#[instruction(creator_id: String)]
+pub struct TipCreator<'info> {
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = 8 + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", creator_id.as_bytes()],
+        bump
+    )]
+    pub creator_vault: SystemAccount<'info>,
+
+    // Token tips only
+    pub mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = tipper
+    )]
+    pub tipper_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        associated_token::mint = mint,
+        associated_token::authority = creator_vault
+    )]
+    pub vault_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> TipCreator<'info> {
+    pub fn tip_creator(
+        &mut self,
+        creator_id: String,
+        amount: u64,
+        memo: Option<String>,
+        bumps: &TipCreatorBumps
+    ) -> Result<()> {
+        require!(creator_id.len() <= MAX_CREATOR_ID_LEN, CurveError::CreatorIdTooLong);
+        require!(
+            memo.as_ref().map_or(0, |m| m.len()) <= MAX_TIP_MEMO_LEN,
+            CurveError::MemoTooLong
+        );
+
+        if self.creator_profile.creator_id.is_empty() {
+            self.creator_profile.version = CURRENT_VERSION;
+            self.creator_profile.creator_id = creator_id.clone();
+            self.creator_profile.last_activity = Clock::get()?.unix_timestamp;
+            self.creator_profile.bump = bumps.creator_profile;
+        }
+
+        let mint = match &self.mint {
+            Some(mint) => {
+                let (Some(tipper_ata), Some(vault_ata)) = (&self.tipper_ata, &self.vault_ata) else {
+                    return err!(CurveError::MissingTipAccounts);
+                };
+
+                let accounts = token::Transfer {
+                    from: tipper_ata.to_account_info(),
+                    to: vault_ata.to_account_info(),
+                    authority: self.tipper.to_account_info()
+                };
+                let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+                token::transfer(cpi_ctx, amount)?;
+
+                Some(mint.key())
+            },
+            None => {
+                // A vault no pool has funded yet is topped up to rent exemption first, or
+                // tips below it couldn't be paid in. Only the tip counts towards the totals
+                let rent = Rent::get()?.minimum_balance(0).saturating_sub(self.creator_vault.lamports());
+
+                let accounts = system_program::Transfer {
+                    from: self.tipper.to_account_info(),
+                    to: self.creator_vault.to_account_info()
+                };
+                let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+                system_program::transfer(cpi_ctx, amount.checked_add(rent).ok_or(CurveError::Overflow)?)?;
+
+                self.creator_profile.total_sol_tips = self.creator_profile.total_sol_tips
+                    .checked_add(amount).ok_or(CurveError::Overflow)?;
+
+                None
+            }
+        };
+
+        self.creator_profile.tip_count = self.creator_profile.tip_count
+            .checked_add(1).ok_or(CurveError::Overflow)?;
+
+        emit!(CreatorTipped {
+            creator_id,
+            tipper: self.tipper.key(),
+            mint,
+            amount,
+            memo
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
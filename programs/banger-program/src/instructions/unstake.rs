@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        transfer,
+        Mint,
+        Token,
+        TokenAccount,
+        Transfer
+    }};
+use crate::state::{Pool, Stake, MIN_STAKE_DURATION};
+use crate::errors::CurveError;
+
+// Rewards earned so far stay pending on the stake account
+#[derive(Accounts)]
+pub struct UnstakeTokens<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = mint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake.bump,
+        has_one = owner
+    )]
+    pub stake: Box<Account<'info, Stake>>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: used for signing
+    #[account(
+        seeds = [b"authority"],
+        bump = pool.authority_bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> UnstakeTokens<'info> {
+    pub fn unstake(&mut self, amount: u64) -> Result<()> {
+        let unlock = self.stake.staked_at.checked_add(MIN_STAKE_DURATION).ok_or(CurveError::Overflow)?;
+        require!(Clock::get()?.unix_timestamp >= unlock, CurveError::StakeLocked);
+
+        let staked = self.stake.amount.checked_sub(amount).ok_or(CurveError::InsufficientStake)?;
+        self.stake.set_amount(staked, self.pool.reward_per_share)?;
+        self.pool.total_staked = self.pool.total_staked.checked_sub(amount).ok_or(CurveError::Overflow)?;
+
+        // Empty stake accounts are closed so they stop holding the pool open
+        if self.stake.is_empty() {
+            self.pool.stakers = self.pool.stakers.checked_sub(1).ok_or(CurveError::Overflow)?;
+            self.stake.close(self.owner.to_account_info())?;
+        }
+
+        let seeds = &[
+            &b"authority"[..],
+            &[self.pool.authority_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: self.stake_vault.to_account_info(),
+            to: self.owner_ata.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address, token::{Mint, TokenAccount}};
+use crate::state::{Pool, Stake, HolderBadge, CURRENT_VERSION};
+use crate::errors::CurveError;
+
+// Anyone can refresh a holder's badge, the payer only covers rent on creation
+#[derive(Accounts)]
+pub struct VerifyHolding<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub holder: SystemAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"pool", mint.key().as_ref()],
+        bump = pool.bump,
+        has_one = mint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: the holder's ATA, read in the handler since a closed one counts as empty
+    #[account(
+        address = get_associated_token_address(&holder.key(), &mint.key())
+    )]
+    pub holder_ata: UncheckedAccount<'info>,
+
+    // Counts staked tokens towards the tier when passed
+    #[account(
+        seeds = [b"stake", pool.key().as_ref(), holder.key().as_ref()],
+        bump = stake.bump
+    )]
+    pub stake: Option<Box<Account<'info, Stake>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + HolderBadge::INIT_SPACE,
+        seeds = [b"badge", pool.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub holder_badge: Box<Account<'info, HolderBadge>>,
+
+    pub system_program: Program<'info, System>
+}
+
+impl<'info> VerifyHolding<'info> {
+    pub fn verify_holding(&mut self, bumps: &VerifyHoldingBumps) -> Result<()> {
+        let held = match self.holder_ata.data_is_empty() {
+            true => 0,
+            false => TokenAccount::try_deserialize(&mut &self.holder_ata.try_borrow_data()?[..])?.amount
+        };
+        let staked = self.stake.as_ref().map_or(0, |stake| stake.amount);
+        let balance = held.checked_add(staked).ok_or(CurveError::Overflow)?;
+
+        let tier = self.pool.holder_tier(balance);
+
+        msg!("holder: {}, balance: {}, tier: {}", self.holder.key(), balance, tier);
+
+        self.holder_badge.set_inner(HolderBadge {
+            version: CURRENT_VERSION,
+            holder: self.holder.key(),
+            pool: self.pool.key(),
+            tier,
+            balance,
+            verified_at: Clock::get()?.unix_timestamp,
+            bump: bumps.holder_badge,
+            reserved: self.holder_badge.reserved
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
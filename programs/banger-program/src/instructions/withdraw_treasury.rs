@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{Transfer, transfer};
+use crate::state::Config;
+use crate::errors::CurveError;
+
+// When a treasury split is configured the recipients are passed as
+// remaining accounts, in the same order as the split
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+impl<'info> WithdrawTreasury<'info> {
+    pub fn withdraw_treasury(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+        bumps: &WithdrawTreasuryBumps
+    ) -> Result<()> {
+        // The treasury PDA has to stay rent exempt to keep receiving fees
+        let available = self.treasury.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        require!(amount <= available, CurveError::InsufficientTreasury);
+
+        let seeds = &[
+            &b"treasury"[..],
+            &[bumps.treasury]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let split = &self.config.treasury_split;
+
+        if split.is_empty() {
+            return self.pay(self.admin.to_account_info(), amount, signer_seeds);
+        }
+
+        require!(
+            remaining_accounts.len() == split.len(),
+            CurveError::TreasuryRecipientMismatch
+        );
+
+        let mut paid: u64 = 0;
+        for (i, (share, recipient)) in split.iter().zip(remaining_accounts.iter()).enumerate() {
+            require_keys_eq!(recipient.key(), share.recipient, CurveError::TreasuryRecipientMismatch);
+
+            // The last recipient takes the rounding dust
+            let portion = if i == split.len() - 1 {
+                amount.checked_sub(paid).ok_or(CurveError::Overflow)?
+            } else {
+                amount
+                    .checked_mul(share.share as u64).ok_or(CurveError::Overflow)?
+                    .checked_div(10000).ok_or(CurveError::Overflow)?
+            };
+            paid = paid.checked_add(portion).ok_or(CurveError::Overflow)?;
+
+            self.pay(recipient.clone(), portion, signer_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    fn pay(&self, to: AccountInfo<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let accounts = Transfer {
+            from: self.treasury.to_account_info(),
+            to
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address, AssociatedToken},
+    token::{
+        transfer,
+        Mint,
+        Token,
+        TokenAccount,
+        Transfer
+    }};
+use crate::state::Config;
+use crate::errors::CurveError;
+
+// Fees from quote token pools collect in the treasury's ATA for each quote mint.
+// When a treasury split is configured the recipients' ATAs for the mint are
+// passed as remaining accounts, in the same order as the split, and the admin's
+// ATA is only needed without one
+#[derive(Accounts)]
+pub struct WithdrawTreasuryTokens<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = admin
+    )]
+    pub admin_ata: Option<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>
+}
+
+impl<'info> WithdrawTreasuryTokens<'info> {
+    pub fn withdraw_treasury_tokens(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+        bumps: &WithdrawTreasuryTokensBumps
+    ) -> Result<()> {
+        require!(amount <= self.treasury_ata.amount, CurveError::InsufficientTreasury);
+
+        let seeds = &[
+            &b"treasury"[..],
+            &[bumps.treasury]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let split = &self.config.treasury_split;
+
+        if split.is_empty() {
+            let admin_ata = self.admin_ata.as_ref().ok_or(CurveError::TreasuryRecipientMismatch)?;
+            return self.pay(admin_ata.to_account_info(), amount, signer_seeds);
+        }
+
+        require!(
+            remaining_accounts.len() == split.len(),
+            CurveError::TreasuryRecipientMismatch
+        );
+
+        let mut paid: u64 = 0;
+        for (i, (share, recipient_ata)) in split.iter().zip(remaining_accounts.iter()).enumerate() {
+            require_keys_eq!(
+                recipient_ata.key(),
+                get_associated_token_address(&share.recipient, &self.mint.key()),
+                CurveError::TreasuryRecipientMismatch
+            );
+
+            // The last recipient takes the rounding dust
+            let portion = if i == split.len() - 1 {
+                amount.checked_sub(paid).ok_or(CurveError::Overflow)?
+            } else {
+                amount
+                    .checked_mul(share.share as u64).ok_or(CurveError::Overflow)?
+                    .checked_div(10000).ok_or(CurveError::Overflow)?
+            };
+            paid = paid.checked_add(portion).ok_or(CurveError::Overflow)?;
+
+            self.pay(recipient_ata.clone(), portion, signer_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    fn pay(&self, to: AccountInfo<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let accounts = Transfer {
+            from: self.treasury_ata.to_account_info(),
+            to,
+            authority: self.treasury.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)
+    }
+}
\ No newline at end of file
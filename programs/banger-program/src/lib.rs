@@ -2,8 +2,11 @@ use anchor_lang::prelude::*;
 mod state;
 mod instructions;
 mod errors;
+mod events;
 
 use instructions::*;
+pub use instructions::BasketLeg;
+pub use state::{HolderBadge, LaunchSettings, OrderSide, Pool, Position, ReclaimTarget, TreasuryShare, VaultExpiry, PAUSE_ALL, PAUSE_BUY, PAUSE_CLAIM, PAUSE_SELL};
 
 declare_id!("CYu4zKWC3tNGaJkime2PagfjPCski5YAvS8DwhX25d3o");
 
@@ -11,6 +14,12 @@ declare_id!("CYu4zKWC3tNGaJkime2PagfjPCski5YAvS8DwhX25d3o");
 pub mod banger_program {
     use super::*;
 
+    pub fn init_config(
+        ctx: Context<InitConfig>
+    ) -> Result<()> {
+        ctx.accounts.init_config(&ctx.bumps)
+    }
+
     pub fn init_curve(
         ctx: Context<InitCurve>,
         pow: u64,
@@ -19,15 +28,52 @@ pub mod banger_program {
         ctx.accounts.init_curve(pow, frac)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn init_pool(
         ctx: Context<InitPool>,
         creator_id: String,
         creator_fee: u16,
         banger_fee: u16,
+        decimals: u8,
+        token_name: String,
+        token_metadata_uri: String,
+        launch: LaunchSettings,
+    ) -> Result<()> {
+        ctx.accounts.init_pool(creator_id, creator_fee, banger_fee, decimals, token_name, token_metadata_uri, launch, &ctx.bumps)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn launch_creator_pool(
+        ctx: Context<InitPool>,
+        creator_id: String,
+        creator_fee: u16,
+        banger_fee: u16,
+        decimals: u8,
         token_name: String,
         token_metadata_uri: String,
+        launch: LaunchSettings,
     ) -> Result<()> {
-        ctx.accounts.init_pool(creator_id, creator_fee, banger_fee, token_name, token_metadata_uri, &ctx.bumps)
+        ctx.accounts.launch_creator_pool(creator_id, creator_fee, banger_fee, decimals, token_name, token_metadata_uri, launch, &ctx.bumps)
+    }
+
+    pub fn close_pool(
+        ctx: Context<ClosePool>
+    ) -> Result<()> {
+        ctx.accounts.close_pool()
+    }
+
+    pub fn migrate_pool(
+        ctx: Context<MigratePool>,
+        creator_id: String,
+    ) -> Result<()> {
+        ctx.accounts.migrate_pool(creator_id, &ctx.bumps)
+    }
+
+    pub fn set_launch_settings(
+        ctx: Context<SetLaunchSettings>,
+        launch: LaunchSettings
+    ) -> Result<()> {
+        ctx.accounts.set_launch_settings(launch)
     }
 
     pub fn buy(
@@ -35,7 +81,15 @@ pub mod banger_program {
         amount_in: u64,
         num_mint: u64,
     ) -> Result<()> {
-        ctx.accounts.buy(amount_in, num_mint)
+        ctx.accounts.buy(amount_in, num_mint, &ctx.bumps)
+    }
+
+    pub fn buy_basket<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyBasket<'info>>,
+        legs: Vec<BasketLeg>,
+        amount_in: u64,
+    ) -> Result<()> {
+        ctx.accounts.buy_basket(legs, amount_in, ctx.remaining_accounts, &ctx.bumps)
     }
 
     pub fn sell(
@@ -43,13 +97,274 @@ pub mod banger_program {
         num_burn: u64,
         amount_out: u64,
     ) -> Result<()> {
-        ctx.accounts.sell(num_burn, amount_out)
+        ctx.accounts.sell(num_burn, amount_out, &ctx.bumps)
+    }
+
+    pub fn swap_creator_tokens(
+        ctx: Context<SwapCreatorTokens>,
+        num_burn: u64,
+        min_out: u64,
+    ) -> Result<()> {
+        ctx.accounts.swap_creator_tokens(num_burn, min_out, &ctx.bumps)
+    }
+
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        id: u64,
+        side: OrderSide,
+        amount: u64,
+        trigger_price: u64,
+        limit: u64,
+        keeper_tip: u64,
+    ) -> Result<()> {
+        ctx.accounts.place_order(id, side, amount, trigger_price, limit, keeper_tip, &ctx.bumps)
+    }
+
+    pub fn execute_order(
+        ctx: Context<ExecuteOrder>
+    ) -> Result<()> {
+        ctx.accounts.execute_order()
+    }
+
+    pub fn cancel_order(
+        ctx: Context<CancelOrder>
+    ) -> Result<()> {
+        ctx.accounts.cancel_order(&ctx.bumps)
+    }
+
+    pub fn open_dca(
+        ctx: Context<OpenDca>,
+        lamports_per_interval: u64,
+        interval: i64,
+        max_price: u64,
+        deposit: u64,
+    ) -> Result<()> {
+        ctx.accounts.open_dca(lamports_per_interval, interval, max_price, deposit, &ctx.bumps)
+    }
+
+    pub fn execute_dca(
+        ctx: Context<ExecuteDca>
+    ) -> Result<()> {
+        ctx.accounts.execute_dca()
+    }
+
+    pub fn close_dca(
+        ctx: Context<CloseDca>
+    ) -> Result<()> {
+        ctx.accounts.close_dca()
+    }
+
+    pub fn set_staking_share(
+        ctx: Context<SetStakingShare>,
+        staking_share: u16,
+    ) -> Result<()> {
+        ctx.accounts.set_staking_share(staking_share)
+    }
+
+    pub fn stake(
+        ctx: Context<StakeTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.stake(amount, &ctx.bumps)
+    }
+
+    pub fn unstake(
+        ctx: Context<UnstakeTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.unstake(amount)
+    }
+
+    pub fn claim_rewards(
+        ctx: Context<ClaimRewards>
+    ) -> Result<()> {
+        ctx.accounts.claim_rewards()
+    }
+
+    pub fn set_holder_tiers(
+        ctx: Context<SetHolderTiers>,
+        tiers: Vec<u64>,
+    ) -> Result<()> {
+        ctx.accounts.set_holder_tiers(tiers)
+    }
+
+    pub fn verify_holding(
+        ctx: Context<VerifyHolding>
+    ) -> Result<()> {
+        ctx.accounts.verify_holding(&ctx.bumps)
     }
 
     pub fn claim(
         ctx: Context<Claim>,
         creator_id: String
     ) -> Result<()> {
-        ctx.accounts.claim(creator_id)
+        ctx.accounts.claim(creator_id, &ctx.bumps)
+    }
+
+    pub fn tip_creator(
+        ctx: Context<TipCreator>,
+        creator_id: String,
+        amount: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        ctx.accounts.tip_creator(creator_id, amount, memo, &ctx.bumps)
+    }
+
+    pub fn claim_tokens(
+        ctx: Context<ClaimTokens>,
+        creator_id: String
+    ) -> Result<()> {
+        ctx.accounts.claim_tokens(creator_id, &ctx.bumps)
+    }
+
+    pub fn set_vault_expiry(
+        ctx: Context<SetVaultExpiry>,
+        vault_expiry: VaultExpiry
+    ) -> Result<()> {
+        ctx.accounts.set_vault_expiry(vault_expiry)
+    }
+
+    pub fn reclaim_vault(
+        ctx: Context<ReclaimVault>,
+        creator_id: String
+    ) -> Result<()> {
+        ctx.accounts.reclaim_vault(creator_id, &ctx.bumps)
+    }
+
+    pub fn pause(
+        ctx: Context<SetPause>,
+        flags: u8
+    ) -> Result<()> {
+        ctx.accounts.pause(flags)
+    }
+
+    pub fn unpause(
+        ctx: Context<SetPause>,
+        flags: u8
+    ) -> Result<()> {
+        ctx.accounts.unpause(flags)
+    }
+
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+        new_admin: Option<Pubkey>
+    ) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(
+        ctx: Context<AcceptAdmin>
+    ) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn set_roles(
+        ctx: Context<SetRoles>,
+        fee_manager: Pubkey,
+        pauser: Pubkey,
+        creator_verifier: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.set_roles(fee_manager, pauser, creator_verifier)
+    }
+
+    pub fn set_pool_fees(
+        ctx: Context<SetPoolFees>,
+        creator_fee: u16,
+        banger_fee: u16
+    ) -> Result<()> {
+        ctx.accounts.set_pool_fees(creator_fee, banger_fee)
+    }
+
+    pub fn set_treasury_split(
+        ctx: Context<SetTreasurySplit>,
+        split: Vec<TreasuryShare>
+    ) -> Result<()> {
+        ctx.accounts.set_treasury_split(split)
+    }
+
+    pub fn withdraw_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTreasury<'info>>,
+        amount: u64
+    ) -> Result<()> {
+        ctx.accounts.withdraw_treasury(amount, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn withdraw_treasury_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTreasuryTokens<'info>>,
+        amount: u64
+    ) -> Result<()> {
+        ctx.accounts.withdraw_treasury_tokens(amount, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn set_referral_fee(
+        ctx: Context<SetReferralFee>,
+        referral_fee: u16
+    ) -> Result<()> {
+        ctx.accounts.set_referral_fee(referral_fee)
+    }
+
+    pub fn init_referral(
+        ctx: Context<InitReferral>
+    ) -> Result<()> {
+        ctx.accounts.init_referral(&ctx.bumps)
+    }
+
+    pub fn claim_referral(
+        ctx: Context<ClaimReferral>
+    ) -> Result<()> {
+        ctx.accounts.claim_referral()
+    }
+
+    pub fn link_creator(
+        ctx: Context<LinkCreator>,
+        creator_id: String,
+        wallet: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.link_creator(creator_id, wallet, &ctx.bumps)
+    }
+
+    pub fn claim_pool(
+        ctx: Context<ClaimPool>
+    ) -> Result<()> {
+        ctx.accounts.claim_pool()
+    }
+
+    pub fn reserve_creator_allocation(
+        ctx: Context<ReserveCreatorAllocation>,
+        amount: u64,
+        amount_in: u64,
+        vesting_duration: i64
+    ) -> Result<()> {
+        ctx.accounts.reserve_creator_allocation(amount, amount_in, vesting_duration, &ctx.bumps)
+    }
+
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        beneficiary: Pubkey,
+        total: u64,
+        start: i64,
+        cliff: i64,
+        duration: i64
+    ) -> Result<()> {
+        ctx.accounts.create_vesting(beneficiary, total, start, cliff, duration, &ctx.bumps)
+    }
+
+    pub fn claim_vested(
+        ctx: Context<ClaimVested>
+    ) -> Result<()> {
+        ctx.accounts.claim_vested(&ctx.bumps)
+    }
+
+    pub fn revoke_unvested(
+        ctx: Context<RevokeUnvested>
+    ) -> Result<()> {
+        ctx.accounts.revoke_unvested(&ctx.bumps)
+    }
+
+    pub fn get_twap(
+        ctx: Context<GetTwap>,
+        window: i64
+    ) -> Result<u64> {
+        ctx.accounts.get_twap(window)
     }
 }
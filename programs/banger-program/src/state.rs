@@ -1,31 +1,792 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use crate::errors::CurveError;
+
+/// Largest number of decimals a pool mint may be created with.
+pub const MAX_DECIMALS: u8 = 9;
+
+// Pause flags, usable both globally on `Config` and per `Pool`
+pub const PAUSE_BUY: u8 = 1 << 0;
+pub const PAUSE_SELL: u8 = 1 << 1;
+pub const PAUSE_CLAIM: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_BUY | PAUSE_SELL | PAUSE_CLAIM;
+
+/// Longest creator id accepted, also bounded by the 32 byte seed limit.
+pub const MAX_CREATOR_ID_LEN: usize = 32;
+
+/// Largest allocation, in whole tokens, a creator can reserve at launch.
+pub const MAX_CREATOR_ALLOCATION: u64 = 100;
+
+/// Number of trade observations kept per pool for TWAP queries.
+pub const OBSERVATIONS_LEN: usize = 32;
+
+/// Minimum seconds between two stored observations, so the buffer spans at least
+/// `OBSERVATIONS_LEN - 2` of these however often the pool trades.
+pub const OBSERVATION_INTERVAL: i64 = 60;
+
+/// Most recipients a treasury withdrawal can be split across.
+pub const MAX_TREASURY_RECIPIENTS: usize = 4;
+
+/// Longest memo accepted on a tip.
+pub const MAX_TIP_MEMO_LEN: usize = 128;
+
+/// Most holder tiers a creator can configure on a pool.
+pub const MAX_HOLDER_TIERS: usize = 4;
+
+/// Layout version written to newly created and migrated accounts.
+pub const CURRENT_VERSION: u8 = 1;
+
+/// Bytes left free at the end of every account for future fields.
+pub const RESERVED_SPACE: usize = 64;
+
+/// Fixed point scale of the staking reward-per-share accumulator.
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Seconds staked tokens stay locked after the last stake, so stakers can't
+/// stake around a single trade to take its fees.
+pub const MIN_STAKE_DURATION: i64 = 24 * 60 * 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct TreasuryShare {
+    pub recipient: Pubkey,
+    pub share: u16 // basis points
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ReclaimTarget {
+    Treasury,
+    Stakers // falls back to the treasury when the pool has no SOL stakers
+}
+
+// Creator vaults idle for `period` seconds can be swept to `target`, a zero period disables it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct VaultExpiry {
+    pub period: i64,
+    pub target: ReclaimTarget
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub fee_manager: Pubkey,
+    pub pauser: Pubkey,
+    pub creator_verifier: Pubkey,
+    #[max_len(MAX_TREASURY_RECIPIENTS)]
+    pub treasury_split: Vec<TreasuryShare>,
+    pub referral_fee: u16, // basis points of the banger fee
+    pub vault_expiry: VaultExpiry,
+    pub paused: u8,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+impl Config {
+    pub fn check_not_paused(&self, flag: u8) -> Result<()> {
+        require!(self.paused & flag == 0, CurveError::Paused);
+        Ok(())
+    }
+
+    /// Splits a banger fee into the treasury and referrer portions.
+    pub fn split_referral_fee(&self, banger_fee: u64) -> Result<(u64, u64)> {
+        let referral = banger_fee
+            .checked_mul(self.referral_fee as u64).ok_or(CurveError::Overflow)?
+            .checked_div(10000).ok_or(CurveError::Overflow)?;
+
+        Ok((banger_fee - referral, referral))
+    }
+}
+
+// Optional anti-bot limits, zero disables each one
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct LaunchSettings {
+    pub trading_start: i64,
+    pub launch_slots: u64,
+    pub max_wallet_launch: u64, // per wallet while within `launch_slots` of the first buy
+    pub max_per_tx: u64
+}
+
+// Leaderboard counters. `hourly_volume[h % 24]` holds the volume of hour `h`,
+// only the 24 hours up to `last_hour` are current. Volumes are in the pool's
+// quote unit, lamports or base units of the quote mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct PoolStats {
+    pub volume: u64,
+    pub trade_count: u64,
+    pub holders: u64,
+    pub hourly_volume: [u64; 24],
+    pub last_hour: i64
+}
+
+impl PoolStats {
+    pub fn record_trade(&mut self, now: i64, volume: u64) -> Result<()> {
+        self.volume = self.volume.checked_add(volume).ok_or(CurveError::Overflow)?;
+        self.trade_count = self.trade_count.checked_add(1).ok_or(CurveError::Overflow)?;
+
+        // Clear the buckets of the hours without trades since the last one
+        let hour = now / 3600;
+        let stale = (hour - self.last_hour).clamp(0, 24);
+        for h in (hour - stale + 1)..=hour {
+            self.hourly_volume[h.rem_euclid(24) as usize] = 0;
+        }
+        self.last_hour = self.last_hour.max(hour);
+
+        let bucket = &mut self.hourly_volume[hour.rem_euclid(24) as usize];
+        *bucket = bucket.checked_add(volume).ok_or(CurveError::Overflow)?;
+
+        Ok(())
+    }
+}
 
 #[account]
+#[derive(InitSpace)]
 pub struct Pool {
+    pub version: u8,
     pub admin: Pubkey,
+    pub payer: Pubkey, // paid the rent, gets it back on close
     pub mint: Pubkey,
     pub curve: Pubkey,
     pub treasury: Pubkey,
+    pub quote_mint: Option<Pubkey>, // None for SOL pools
+    #[max_len(MAX_CREATOR_ID_LEN)]
     pub creator_id: String,
     pub creator_fee: u16,
     pub banger_fee: u16,
+    pub total_creator_fees: u64, // in the pool's quote unit, like every amount it records
+    pub total_banger_fees: u64,
+    pub verified: bool,
+    pub launch: LaunchSettings,
+    pub launch_slot: u64, // slot of the first buy, 0 until then
+    pub stats: PoolStats,
+    pub staking_share: u16, // bps of the creator fee paid to stakers
+    pub total_staked: u64,
+    pub stakers: u32, // open stake accounts, some may only hold unclaimed rewards
+    pub reward_per_share: u128,
+    #[max_len(MAX_HOLDER_TIERS)]
+    pub holder_tiers: Vec<u64>, // ascending token thresholds
+    pub paused: u8,
     pub bump: u8,
-    pub authority_bump: u8
+    pub authority_bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+impl Pool {
+    pub fn check_not_paused(&self, config: &Config, flag: u8) -> Result<()> {
+        config.check_not_paused(flag)?;
+        require!(self.paused & flag == 0, CurveError::Paused);
+        Ok(())
+    }
+
+    /// Enforces the launch settings on a buy of `amount` by the owner of `position`.
+    pub fn check_launch_limits(&mut self, position: &mut Position, amount: u64, clock: &Clock) -> Result<()> {
+        require!(clock.unix_timestamp >= self.launch.trading_start, CurveError::TradingNotStarted);
+
+        // The per-wallet window opens with trading, not with the pool. A creator
+        // allocation counts as a trade but doesn't open it
+        if self.launch_slot == 0 {
+            self.launch_slot = clock.slot;
+        }
+
+        if self.launch.max_per_tx > 0 {
+            require!(amount <= self.launch.max_per_tx, CurveError::LaunchLimitExceeded);
+        }
+
+        let launch_end = self.launch_slot.checked_add(self.launch.launch_slots).ok_or(CurveError::Overflow)?;
+        if self.launch.max_wallet_launch > 0 && clock.slot < launch_end {
+            position.launch_bought = position.launch_bought.checked_add(amount).ok_or(CurveError::Overflow)?;
+            require!(position.launch_bought <= self.launch.max_wallet_launch, CurveError::LaunchLimitExceeded);
+        }
+
+        Ok(())
+    }
+
+    /// A curve amount in lamports as paid in this pool: unchanged for SOL pools, the same
+    /// number of whole tokens in base units of the quote mint for quote pools.
+    pub fn to_quote(&self, lamports: u64, quote_decimals: Option<u8>, round_up: bool) -> Result<u64> {
+        if self.quote_mint.is_none() {
+            return Ok(lamports);
+        }
+
+        let decimals = quote_decimals.ok_or(CurveError::MissingQuoteAccounts)?;
+        let unit = 10u128.checked_pow(decimals as u32).ok_or(CurveError::Overflow)?;
+        mul_div(lamports as u128, unit, LAMPORTS_PER_SOL as u128, round_up)
+    }
+
+    /// Creator and banger fees charged on a trade worth `amount`, in lamports or quote base units.
+    pub fn fees(&self, amount: u64) -> Result<(u64, u64)> {
+        let creator_fee = amount
+            .checked_mul(self.creator_fee as u64).ok_or(CurveError::Overflow)?
+            .checked_div(10000).ok_or(CurveError::Overflow)?;
+
+        let banger_fee = amount
+            .checked_mul(self.banger_fee as u64).ok_or(CurveError::Overflow)?
+            .checked_div(10000).ok_or(CurveError::Overflow)?;
+
+        Ok((creator_fee, banger_fee))
+    }
+
+    // Spreads lamports over current stakers, returns false when there are none
+    pub fn distribute_rewards(&mut self, amount: u64) -> Result<bool> {
+        if self.total_staked == 0 {
+            return Ok(false);
+        }
+
+        let increment = (amount as u128)
+            .checked_mul(REWARD_PRECISION).ok_or(CurveError::Overflow)?
+            .checked_div(self.total_staked as u128).ok_or(CurveError::Overflow)?;
+        self.reward_per_share = self.reward_per_share.checked_add(increment).ok_or(CurveError::Overflow)?;
+
+        Ok(true)
+    }
+
+    // Everything outside buy, sell and staking only moves lamports
+    pub fn check_sol_quoted(&self) -> Result<()> {
+        require!(self.quote_mint.is_none(), CurveError::QuotePoolUnsupported);
+        Ok(())
+    }
+
+    // Number of thresholds the balance meets, 0 when below the first tier
+    pub fn holder_tier(&self, balance: u64) -> u8 {
+        self.holder_tiers.iter().filter(|&&t| balance >= t).count() as u8
+    }
+
+    // Referrers are paid out of the banger fee, so that part never reaches the treasury
+    pub fn record_referral_fee(&mut self, referral_fee: u64) -> Result<()> {
+        self.total_banger_fees = self.total_banger_fees.checked_sub(referral_fee).ok_or(CurveError::Overflow)?;
+        Ok(())
+    }
+
+    // Returns the stakers' share of the creator fee, which stays in the pool account
+    // until claimed and should not be sent to the creator vault
+    pub fn record_fees(&mut self, creator_fee: u64, banger_fee: u64) -> Result<u64> {
+        let staking_reward = match self.total_staked {
+            0 => 0,
+            _ => mul_div(creator_fee as u128, self.staking_share as u128, 10000, false)?
+        };
+
+        if staking_reward > 0 {
+            self.distribute_rewards(staking_reward)?;
+        }
+
+        self.total_creator_fees = self.total_creator_fees.checked_add(creator_fee - staking_reward).ok_or(CurveError::Overflow)?;
+        self.total_banger_fees = self.total_banger_fees.checked_add(banger_fee).ok_or(CurveError::Overflow)?;
+        Ok(staking_reward)
+    }
+}
+
+// One per creator id, created by whichever comes first of the pool or the
+// verifier linking the creator's wallet
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorProfile {
+    pub version: u8,
+    #[max_len(MAX_CREATOR_ID_LEN)]
+    pub creator_id: String,
+    pub mint: Option<Pubkey>,
+    pub wallet: Option<Pubkey>,
+    pub total_sol_tips: u64, // lamports, token tips only show up in tip_count and the CreatorTipped events
+    pub tip_count: u64,
+    pub last_activity: i64, // last creation, link or claim
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+// What a wallet has traded through the curve of one pool. Tokens received
+// outside the curve are not tracked, so `balance` can be below the ATA balance.
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub balance: u64,
+    pub cost_basis: u64, // paid for the current balance in the pool's quote unit, fees included
+    pub avg_entry_price: u64, // quote unit per whole token
+    pub spent: u64, // in the pool's quote unit, like the cost basis
+    pub received: u64,
+    pub realized_pnl: i64,
+    pub launch_bought: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+impl Position {
+    pub fn init_if_needed(&mut self, owner: Pubkey, pool: Pubkey, bump: u8) {
+        if self.owner == Pubkey::default() {
+            self.version = CURRENT_VERSION;
+            self.owner = owner;
+            self.pool = pool;
+            self.bump = bump;
+        }
+    }
+
+    pub fn record_buy(&mut self, amount: u64, cost: u64, decimals: u8) -> Result<()> {
+        self.balance = self.balance.checked_add(amount).ok_or(CurveError::Overflow)?;
+        self.cost_basis = self.cost_basis.checked_add(cost).ok_or(CurveError::Overflow)?;
+        self.spent = self.spent.checked_add(cost).ok_or(CurveError::Overflow)?;
+
+        self.update_avg_entry_price(decimals)
+    }
+
+    pub fn record_sell(&mut self, amount: u64, proceeds: u64, decimals: u8) -> Result<()> {
+        self.received = self.received.checked_add(proceeds).ok_or(CurveError::Overflow)?;
+
+        // Only the part of the sale bought through the curve has a cost basis
+        let matched = amount.min(self.balance);
+        if matched == 0 {
+            return Ok(());
+        }
+
+        let basis_sold = mul_div(self.cost_basis as u128, matched as u128, self.balance as u128, false)?;
+        let matched_proceeds = mul_div(proceeds as u128, matched as u128, amount as u128, false)?;
+
+        let pnl = (matched_proceeds as i128) - (basis_sold as i128);
+        self.realized_pnl = i64::try_from((self.realized_pnl as i128) + pnl).map_err(|_| CurveError::Overflow)?;
+
+        self.balance -= matched;
+        self.cost_basis -= basis_sold;
+
+        self.update_avg_entry_price(decimals)
+    }
+
+    fn update_avg_entry_price(&mut self, decimals: u8) -> Result<()> {
+        self.avg_entry_price = if self.balance == 0 {
+            0
+        } else {
+            mul_div(self.cost_basis as u128, 10u128.pow(decimals as u32), self.balance as u128, false)?
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OrderSide {
+    Buy,
+    Sell
+}
+
+// A buy escrows `limit + keeper_tip` lamports in this account; a sell escrows
+// `amount` tokens in an authority owned token account and `keeper_tip` here.
+// `limit` is the most a buy pays and the least a sell receives, fees included.
+#[account]
+#[derive(InitSpace)]
+pub struct Order {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub id: u64,
+    pub side: OrderSide,
+    pub amount: u64,
+    pub trigger_price: u64, // lamports per whole token
+    pub limit: u64,
+    pub keeper_tip: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+// Rewards are settled into `pending` whenever the staked amount changes
+#[account]
+#[derive(InitSpace)]
+pub struct Stake {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub reward_debt: u128,
+    pub pending: u64,
+    pub claimed: u64,
+    pub staked_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+impl Stake {
+    fn accumulated(&self, reward_per_share: u128) -> Result<u128> {
+        Ok((self.amount as u128)
+            .checked_mul(reward_per_share).ok_or(CurveError::Overflow)?
+            .checked_div(REWARD_PRECISION).ok_or(CurveError::Overflow)?)
+    }
+
+    pub fn settle(&mut self, reward_per_share: u128) -> Result<()> {
+        let accumulated = self.accumulated(reward_per_share)?;
+        let accrued = accumulated.checked_sub(self.reward_debt).ok_or(CurveError::Overflow)?;
+        self.pending = self.pending
+            .checked_add(u64::try_from(accrued).map_err(|_| CurveError::Overflow)?)
+            .ok_or(CurveError::Overflow)?;
+        self.reward_debt = accumulated;
+        Ok(())
+    }
+
+    pub fn set_amount(&mut self, amount: u64, reward_per_share: u128) -> Result<()> {
+        self.settle(reward_per_share)?;
+        self.amount = amount;
+        self.reward_debt = self.accumulated(reward_per_share)?;
+        Ok(())
+    }
+
+    // Nothing staked and nothing to claim, so the account can be closed
+    pub fn is_empty(&self) -> bool {
+        self.amount == 0 && self.pending == 0
+    }
+}
+
+// Readable by other programs and the backend to gate perks by tier
+#[account]
+#[derive(InitSpace)]
+pub struct HolderBadge {
+    pub version: u8,
+    pub holder: Pubkey,
+    pub pool: Pubkey,
+    pub tier: u8,
+    pub balance: u64,
+    pub verified_at: i64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+// Lamports above rent exemption are the unspent deposit
+#[account]
+#[derive(InitSpace)]
+pub struct DcaPlan {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub lamports_per_interval: u64,
+    pub interval: i64,
+    pub max_price: u64, // lamports per whole token
+    pub last_executed: i64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub version: u8,
+    pub referrer: Pubkey,
+    pub accrued: u64,
+    pub claimed: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
 }
 
-impl Space for Pool {
-    const INIT_SPACE: usize = 8 + 32*4 + (4+32) + 2*2 + 1*2;
+impl Referral {
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.accrued = self.accrued.checked_add(amount).ok_or(CurveError::Overflow)?;
+        Ok(())
+    }
 }
 
+// Tokens held in an escrow owned by the authority PDA, released linearly from `start`
+// with nothing claimable before `cliff`. One per mint, beneficiary and funder.
 #[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    pub version: u8,
+    pub beneficiary: Pubkey,
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start: i64,
+    pub cliff: i64,
+    pub duration: i64,
+    pub revoked: bool,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+impl VestingSchedule {
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        // Revoking cuts `total` down to what had vested at that point
+        if self.revoked {
+            return Ok(self.total);
+        }
+
+        if now < self.cliff || now <= self.start {
+            return Ok(0);
+        }
+
+        let elapsed = now - self.start;
+        if elapsed >= self.duration {
+            return Ok(self.total);
+        }
+
+        mul_div(self.total as u128, elapsed as u128, self.duration as u128, false)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub price_cumulative: u128, // quote unit per whole token, times seconds
+    pub volume_cumulative: u128 // quote unit
+}
+
+// Ring buffer of cumulative price and volume, at most one entry per `OBSERVATION_INTERVAL`
+#[account]
+#[derive(InitSpace)]
+pub struct Observations {
+    pub version: u8,
+    pub pool: Pubkey,
+    pub index: u16,
+    pub observations: [Observation; OBSERVATIONS_LEN],
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+impl Observations {
+    /// Records a trade of `volume`, where `price` held since the last observation, both in the pool's quote unit.
+    pub fn record(&mut self, now: i64, price: u64, volume: u64) -> Result<()> {
+        let index = self.index as usize;
+        let last = self.observations[index];
+        let before = self.observations[(index + OBSERVATIONS_LEN - 1) % OBSERVATIONS_LEN];
+
+        let elapsed = now.checked_sub(last.timestamp).ok_or(CurveError::Overflow)?;
+        let price_cumulative = (price as u128)
+            .checked_mul(elapsed as u128).ok_or(CurveError::Overflow)?
+            .checked_add(last.price_cumulative).ok_or(CurveError::Overflow)?;
+        let volume_cumulative = last.volume_cumulative.checked_add(volume as u128).ok_or(CurveError::Overflow)?;
+
+        // The latest entry keeps moving forward until it is an interval past the one before,
+        // so a burst of trades can't push older observations out
+        if last.timestamp - before.timestamp >= OBSERVATION_INTERVAL {
+            self.index = ((index + 1) % OBSERVATIONS_LEN) as u16;
+        }
+        self.observations[self.index as usize] = Observation {
+            timestamp: now,
+            price_cumulative,
+            volume_cumulative
+        };
+
+        Ok(())
+    }
+
+    /// Time weighted average price over the last `window` seconds, given the current spot price.
+    pub fn twap(&self, now: i64, price: u64, window: i64) -> Result<u64> {
+        require!(window > 0, CurveError::InvalidTwapWindow);
+
+        let latest_index = self.index as usize;
+        let latest = self.observations[latest_index];
+
+        let target = now.checked_sub(window).ok_or(CurveError::Overflow)?;
+        if target >= latest.timestamp {
+            return Ok(price);
+        }
+
+        let now_cumulative = (price as u128)
+            .checked_mul((now - latest.timestamp) as u128).ok_or(CurveError::Overflow)?
+            .checked_add(latest.price_cumulative).ok_or(CurveError::Overflow)?;
+
+        // Walk back to the newest observation at or before the start of the window
+        let mut i = latest_index;
+        loop {
+            let prev = (i + OBSERVATIONS_LEN - 1) % OBSERVATIONS_LEN;
+            let before = self.observations[prev];
+            require!(prev != latest_index && before.timestamp != 0, CurveError::TwapWindowTooLong);
+
+            if before.timestamp <= target {
+                let after = self.observations[i];
+
+                // Interpolate linearly between the two observations
+                let target_cumulative = (after.price_cumulative - before.price_cumulative)
+                    .checked_mul((target - before.timestamp) as u128).ok_or(CurveError::Overflow)?
+                    .checked_div((after.timestamp - before.timestamp) as u128).ok_or(CurveError::Overflow)?
+                    .checked_add(before.price_cumulative).ok_or(CurveError::Overflow)?;
+
+                let twap = (now_cumulative - target_cumulative) / window as u128;
+                return u64::try_from(twap).map_err(|_| CurveError::Overflow.into());
+            }
+
+            i = prev;
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
 pub struct Curve {
+    pub version: u8,
+    pub pow: u64,
+    pub frac: u64,
+    pub reserved: [u8; RESERVED_SPACE]
+}
+
+impl Curve {
+    /// Lamports a buyer pays to move the supply from `supply` to `supply + amount`.
+    /// Rounded up so the reserve never ends up short.
+    pub fn buy_price(&self, supply: u64, amount: u64, decimals: u8) -> Result<u64> {
+        let to = supply.checked_add(amount).ok_or(CurveError::Overflow)?;
+        self.area(supply, to, decimals, true)
+    }
+
+    /// Lamports paid out for moving the supply from `supply` to `supply - amount`.
+    /// Rounded down so the reserve never ends up short.
+    pub fn sell_price(&self, supply: u64, amount: u64, decimals: u8) -> Result<u64> {
+        let from = supply.checked_sub(amount).ok_or(CurveError::Overflow)?;
+        self.area(from, supply, decimals, false)
+    }
+
+    /// Largest amount `budget` lamports buys from `supply`, and what it costs.
+    pub fn max_buy(&self, supply: u64, budget: u64, decimals: u8) -> Result<(u64, u64)> {
+        let amount = largest(|amount| matches!(
+            self.buy_price(supply, amount, decimals),
+            Ok(cost) if cost <= budget
+        ));
+
+        Ok((amount, self.buy_price(supply, amount, decimals)?))
+    }
+
+    /// Largest amount minted on top of `supply` that keeps the spot price at or below `price`.
+    pub fn max_mint_below(&self, supply: u64, price: u64, decimals: u8) -> u64 {
+        largest(|amount| matches!(
+            supply.checked_add(amount).map(|to| self.spot_price(to, decimals)),
+            Some(Ok(spot)) if spot <= price
+        ))
+    }
+
+    /// Lamports for one whole token at the current supply.
+    pub fn spot_price(&self, supply: u64, decimals: u8) -> Result<u64> {
+        let pow = self.exp(0)?;
+
+        let numerator = U256::from(supply).checked_pow(U256::from(pow)).ok_or(CurveError::Overflow)?;
+        let denominator = self.scale(pow, decimals)?;
+
+        mul_div_wide(numerator, U256::from(LAMPORTS_PER_SOL), denominator, false)
+    }
+
+    /// Rejects decimals too fine for the curve to price every supply a buyer could pay for.
+    pub fn check_decimals(&self, decimals: u8) -> Result<()> {
+        let exp = self.exp(1)?;
+
+        // Any buy costing at most u64::MAX lamports has `numerator * LAMPORTS_PER_SOL`
+        // below `u64::MAX * denominator`, so that bound has to fit
+        let denominator = self.scale(exp, decimals).map_err(|_| CurveError::InvalidDecimals)?
+            .checked_mul(U256::from(exp)).ok_or(CurveError::InvalidDecimals)?;
+        denominator
+            .checked_mul(U256::from(u64::MAX)).ok_or(CurveError::InvalidDecimals)?;
+
+        Ok(())
+    }
+
+    // The price of one whole token at supply `x` (in whole tokens) is `x^pow / frac` SOL.
+    // Over base units `s = x * 10^decimals` the area between `from` and `to` is
+    // `(to^(pow+1) - from^(pow+1)) / ((pow+1) * frac * 10^(decimals*(pow+1)))` SOL.
+    fn area(&self, from: u64, to: u64, decimals: u8, round_up: bool) -> Result<u64> {
+        let exp = self.exp(1)?;
+
+        let upper = U256::from(to).checked_pow(U256::from(exp)).ok_or(CurveError::Overflow)?;
+        let lower = U256::from(from).checked_pow(U256::from(exp)).ok_or(CurveError::Overflow)?;
+        let numerator = upper.checked_sub(lower).ok_or(CurveError::Overflow)?;
+
+        let denominator = self.scale(exp, decimals)?
+            .checked_mul(U256::from(exp)).ok_or(CurveError::Overflow)?;
+
+        mul_div_wide(numerator, U256::from(LAMPORTS_PER_SOL), denominator, round_up)
+    }
+
+    fn exp(&self, add: u64) -> Result<u32> {
+        let exp = self.pow.checked_add(add).ok_or(CurveError::Overflow)?;
+        Ok(u32::try_from(exp).map_err(|_| CurveError::Overflow)?)
+    }
+
+    // `frac * 10^(decimals * exp)`, converting the supply raised to `exp` into whole tokens
+    fn scale(&self, exp: u32, decimals: u8) -> Result<U256> {
+        let digits = (decimals as u32).checked_mul(exp).ok_or(CurveError::Overflow)?;
+
+        Ok(U256::from(10)
+            .checked_pow(U256::from(digits)).ok_or(CurveError::Overflow)?
+            .checked_mul(U256::from(self.frac)).ok_or(CurveError::Overflow)?)
+    }
+}
+
+// Largest `x` for which `fits` holds, given it holds up to some point and not after
+fn largest(fits: impl Fn(u64) -> bool) -> u64 {
+    // Double until it doesn't fit, then binary search below it
+    let mut low: u64 = 0;
+    let mut high: u64 = 1;
+    while fits(high) {
+        low = high;
+        high = high.saturating_mul(2);
+        if low == u64::MAX {
+            break;
+        }
+    }
+
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    low
+}
+
+// Kept out of this module's scope, the macro expects the std `Result` and
+// generates code clippy objects to
+#[allow(clippy::all)]
+mod wide {
+    uint::construct_uint! {
+        /// Wide enough for the curve's powers of the supply at up to `MAX_DECIMALS`.
+        pub struct U256(4);
+    }
+}
+
+pub use wide::U256;
+
+/// `a * b / c`, computed at 256 bits so `a * b` cannot overflow.
+pub fn mul_div(a: u128, b: u128, c: u128, round_up: bool) -> Result<u64> {
+    mul_div_wide(U256::from(a), U256::from(b), U256::from(c), round_up)
+}
+
+fn mul_div_wide(a: U256, b: U256, c: U256, round_up: bool) -> Result<u64> {
+    require!(!c.is_zero(), CurveError::Overflow);
+
+    let (quotient, remainder) = a.checked_mul(b).ok_or(CurveError::Overflow)?.div_mod(c);
+
+    let mut result = quotient;
+    if round_up && !remainder.is_zero() {
+        result = result.checked_add(U256::one()).ok_or(CurveError::Overflow)?;
+    }
+
+    require!(result <= U256::from(u64::MAX), CurveError::Overflow);
+    Ok(result.as_u64())
+}
+
+// Layouts from before accounts carried a version, only read by `migrate_pool`
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PoolV0 {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub treasury: Pubkey,
+    pub creator_id: String,
+    pub creator_fee: u16,
+    pub banger_fee: u16,
+    pub bump: u8,
+    pub authority_bump: u8
+}
+
+impl PoolV0 {
+    pub const SPACE: usize = 8 + 32*4 + (4+32) + 2*2 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CurveV0 {
     pub pow: u64,
     pub frac: u64
 }
 
-impl Space for Curve {
-    const INIT_SPACE: usize = 8 + 8 + 8;
+impl CurveV0 {
+    pub const SPACE: usize = 8 + 8 + 8;
 }
+
 /*
 #[account]
 pub struct CreatorVault {
@@ -36,4 +797,560 @@ pub struct CreatorVault {
 impl Space for CreatorVault {
     const INIT_SPACE: usize = 8 + 8 + 4 + 32;
 }
-*/
\ No newline at end of file
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every variable length field at its maximum, so the serialized account is as large as it gets
+    fn assert_fits<T: AccountSerialize + Space>(account: &T) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + T::INIT_SPACE);
+    }
+
+    fn max_creator_id() -> String {
+        "x".repeat(MAX_CREATOR_ID_LEN)
+    }
+
+    #[test]
+    fn config_size() {
+        assert_fits(&Config {
+            version: CURRENT_VERSION,
+            admin: Pubkey::new_unique(),
+            pending_admin: Some(Pubkey::new_unique()),
+            fee_manager: Pubkey::new_unique(),
+            pauser: Pubkey::new_unique(),
+            creator_verifier: Pubkey::new_unique(),
+            treasury_split: vec![
+                TreasuryShare { recipient: Pubkey::new_unique(), share: u16::MAX };
+                MAX_TREASURY_RECIPIENTS
+            ],
+            referral_fee: u16::MAX,
+            vault_expiry: VaultExpiry { period: i64::MAX, target: ReclaimTarget::Stakers },
+            paused: PAUSE_ALL,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn pool_size() {
+        assert_fits(&Pool {
+            version: CURRENT_VERSION,
+            admin: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            curve: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            quote_mint: Some(Pubkey::new_unique()),
+            creator_id: max_creator_id(),
+            creator_fee: u16::MAX,
+            banger_fee: u16::MAX,
+            total_creator_fees: u64::MAX,
+            total_banger_fees: u64::MAX,
+            verified: true,
+            launch: LaunchSettings {
+                trading_start: i64::MAX,
+                launch_slots: u64::MAX,
+                max_wallet_launch: u64::MAX,
+                max_per_tx: u64::MAX
+            },
+            launch_slot: u64::MAX,
+            stats: PoolStats {
+                volume: u64::MAX,
+                trade_count: u64::MAX,
+                holders: u64::MAX,
+                hourly_volume: [u64::MAX; 24],
+                last_hour: i64::MAX
+            },
+            staking_share: u16::MAX,
+            total_staked: u64::MAX,
+            stakers: u32::MAX,
+            reward_per_share: u128::MAX,
+            holder_tiers: vec![u64::MAX; MAX_HOLDER_TIERS],
+            paused: PAUSE_ALL,
+            bump: u8::MAX,
+            authority_bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn creator_profile_size() {
+        assert_fits(&CreatorProfile {
+            version: CURRENT_VERSION,
+            creator_id: max_creator_id(),
+            mint: Some(Pubkey::new_unique()),
+            wallet: Some(Pubkey::new_unique()),
+            total_sol_tips: u64::MAX,
+            tip_count: u64::MAX,
+            last_activity: i64::MAX,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn position_size() {
+        assert_fits(&Position {
+            version: CURRENT_VERSION,
+            owner: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            balance: u64::MAX,
+            cost_basis: u64::MAX,
+            avg_entry_price: u64::MAX,
+            spent: u64::MAX,
+            received: u64::MAX,
+            realized_pnl: i64::MIN,
+            launch_bought: u64::MAX,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn order_size() {
+        assert_fits(&Order {
+            version: CURRENT_VERSION,
+            owner: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            id: u64::MAX,
+            side: OrderSide::Sell,
+            amount: u64::MAX,
+            trigger_price: u64::MAX,
+            limit: u64::MAX,
+            keeper_tip: u64::MAX,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn stake_size() {
+        assert_fits(&Stake {
+            version: CURRENT_VERSION,
+            owner: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            amount: u64::MAX,
+            reward_debt: u128::MAX,
+            pending: u64::MAX,
+            claimed: u64::MAX,
+            staked_at: i64::MIN,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn holder_badge_size() {
+        assert_fits(&HolderBadge {
+            version: CURRENT_VERSION,
+            holder: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            tier: MAX_HOLDER_TIERS as u8,
+            balance: u64::MAX,
+            verified_at: i64::MAX,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn dca_plan_size() {
+        assert_fits(&DcaPlan {
+            version: CURRENT_VERSION,
+            owner: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            lamports_per_interval: u64::MAX,
+            interval: i64::MAX,
+            max_price: u64::MAX,
+            last_executed: i64::MAX,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn referral_size() {
+        assert_fits(&Referral {
+            version: CURRENT_VERSION,
+            referrer: Pubkey::new_unique(),
+            accrued: u64::MAX,
+            claimed: u64::MAX,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn vesting_schedule_size() {
+        assert_fits(&VestingSchedule {
+            version: CURRENT_VERSION,
+            beneficiary: Pubkey::new_unique(),
+            funder: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            total: u64::MAX,
+            claimed: u64::MAX,
+            start: i64::MAX,
+            cliff: i64::MAX,
+            duration: i64::MAX,
+            revoked: true,
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn observations_size() {
+        let observation = Observation {
+            timestamp: i64::MAX,
+            price_cumulative: u128::MAX,
+            volume_cumulative: u128::MAX
+        };
+
+        assert_fits(&Observations {
+            version: CURRENT_VERSION,
+            pool: Pubkey::new_unique(),
+            index: u16::MAX,
+            observations: [observation; OBSERVATIONS_LEN],
+            bump: u8::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    #[test]
+    fn curve_size() {
+        assert_fits(&Curve {
+            version: CURRENT_VERSION,
+            pow: u64::MAX,
+            frac: u64::MAX,
+            reserved: [u8::MAX; RESERVED_SPACE]
+        });
+    }
+
+    // One whole token at `MAX_DECIMALS`
+    const WHOLE: u64 = 1_000_000_000;
+
+    fn curve(pow: u64) -> Curve {
+        Curve {
+            version: CURRENT_VERSION,
+            pow,
+            frac: 32000,
+            reserved: [0; RESERVED_SPACE]
+        }
+    }
+
+    #[test]
+    fn curve_prices_large_supply_at_max_decimals() {
+        let curve = curve(2);
+        let supply = 10_000 * WHOLE;
+
+        // 10_000^3 / (3 * 32000) SOL, rounded towards the pool
+        assert_eq!(curve.buy_price(0, supply, MAX_DECIMALS).unwrap(), 10_416_666_666_666_667);
+        assert_eq!(curve.sell_price(supply, supply, MAX_DECIMALS).unwrap(), 10_416_666_666_666_666);
+        // 10_000^2 / 32000 SOL
+        assert_eq!(curve.spot_price(supply, MAX_DECIMALS).unwrap(), 3_125_000_000_000);
+
+        // One whole token off the top of a supply costing 18 billion SOL in total
+        let supply = 120_000 * WHOLE;
+        assert_eq!(curve.buy_price(0, supply, MAX_DECIMALS).unwrap(), 18_000_000_000_000_000_000);
+        assert_eq!(curve.sell_price(supply, WHOLE, MAX_DECIMALS).unwrap(), 449_996_250_010_416);
+
+        // Past what u64 lamports can pay for
+        assert!(curve.buy_price(0, 130_000 * WHOLE, MAX_DECIMALS).is_err());
+        assert!(curve.buy_price(0, u64::MAX, MAX_DECIMALS).is_err());
+    }
+
+    #[test]
+    fn curve_prices_higher_powers_at_max_decimals() {
+        // 1000^4 / (4 * 32000) SOL
+        let curve = curve(3);
+        assert_eq!(curve.buy_price(0, 1000 * WHOLE, MAX_DECIMALS).unwrap(), 7_812_500_000_000_000);
+        assert_eq!(curve.sell_price(1000 * WHOLE, 1000 * WHOLE, MAX_DECIMALS).unwrap(), 7_812_500_000_000_000);
+    }
+
+    #[test]
+    fn curve_price_does_not_depend_on_decimals() {
+        let curve = curve(2);
+        for decimals in 0..=MAX_DECIMALS {
+            let whole = 10u64.pow(decimals as u32);
+            assert_eq!(curve.buy_price(0, 10_000 * whole, decimals).unwrap(), 10_416_666_666_666_667);
+        }
+    }
+
+    #[test]
+    fn curve_round_trip_favours_pool() {
+        let curve = curve(2);
+        let supply = 10_000 * WHOLE;
+
+        for amount in [1, 7, WHOLE, 123 * WHOLE + 456] {
+            let cost = curve.buy_price(supply, amount, MAX_DECIMALS).unwrap();
+            let proceeds = curve.sell_price(supply + amount, amount, MAX_DECIMALS).unwrap();
+            assert!(cost - proceeds <= 1);
+        }
+    }
+
+    #[test]
+    fn max_buy_spends_up_to_budget() {
+        let curve = curve(2);
+        let supply = 10_000 * WHOLE;
+        let budget = curve.buy_price(supply, 5 * WHOLE, MAX_DECIMALS).unwrap();
+        assert_eq!(budget, 15_632_813_802_084);
+
+        assert_eq!(curve.max_buy(supply, budget, MAX_DECIMALS).unwrap(), (5 * WHOLE, budget));
+
+        let (amount, cost) = curve.max_buy(supply, budget - 1, MAX_DECIMALS).unwrap();
+        assert!(amount < 5 * WHOLE && cost < budget);
+
+        assert_eq!(curve.max_buy(supply, 0, MAX_DECIMALS).unwrap(), (0, 0));
+        // The whole lamport range, where one more base unit no longer fits in a u64
+        let (amount, cost) = curve.max_buy(0, u64::MAX, MAX_DECIMALS).unwrap();
+        assert!(amount > 120_000 * WHOLE);
+        assert_eq!(cost, curve.buy_price(0, amount, MAX_DECIMALS).unwrap());
+        assert!(curve.buy_price(0, amount + 1, MAX_DECIMALS).is_err());
+    }
+
+    #[test]
+    fn max_mint_below_stops_at_price() {
+        let curve = curve(2);
+        let supply = 10_000 * WHOLE;
+        let price = curve.spot_price(11_000 * WHOLE, MAX_DECIMALS).unwrap();
+
+        let amount = curve.max_mint_below(supply, price, MAX_DECIMALS);
+        assert!(amount >= 1000 * WHOLE);
+        assert!(curve.spot_price(supply + amount, MAX_DECIMALS).unwrap() <= price);
+        assert!(curve.spot_price(supply + amount + 1, MAX_DECIMALS).unwrap() > price);
+
+        // Already above the price
+        assert_eq!(curve.max_mint_below(supply, price / 2, MAX_DECIMALS), 0);
+    }
+
+    #[test]
+    fn check_decimals_caps_against_pow() {
+        for decimals in 0..=MAX_DECIMALS {
+            assert!(curve(2).check_decimals(decimals).is_ok());
+            assert!(curve(3).check_decimals(decimals).is_ok());
+        }
+
+        assert!(curve(5).check_decimals(6).is_ok());
+        assert!(curve(5).check_decimals(MAX_DECIMALS).is_err());
+    }
+
+    #[test]
+    fn mul_div_uses_full_width() {
+        assert_eq!(mul_div(1 << 100, 1 << 90, 1 << 127, false).unwrap(), 1 << 63);
+        assert_eq!(mul_div(10, 1, 3, false).unwrap(), 3);
+        assert_eq!(mul_div(10, 1, 3, true).unwrap(), 4);
+        assert!(mul_div(u128::MAX, 2, 1, false).is_err());
+        assert!(mul_div(1, 1, 0, false).is_err());
+    }
+
+    // `migrate_pool` recognizes old accounts by these sizes
+    #[test]
+    fn legacy_sizes() {
+        let pool = PoolV0 {
+            admin: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            curve: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            creator_id: max_creator_id(),
+            creator_fee: u16::MAX,
+            banger_fee: u16::MAX,
+            bump: u8::MAX,
+            authority_bump: u8::MAX
+        };
+        assert_eq!(8 + pool.try_to_vec().unwrap().len(), PoolV0::SPACE);
+
+        let curve = CurveV0 {
+            pow: u64::MAX,
+            frac: u64::MAX
+        };
+        assert_eq!(8 + curve.try_to_vec().unwrap().len(), CurveV0::SPACE);
+    }
+
+    // As `init_pool` leaves them, with the pool created at `now`
+    fn observations(now: i64) -> Observations {
+        let mut observations = Observations {
+            version: CURRENT_VERSION,
+            pool: Pubkey::new_unique(),
+            index: 0,
+            observations: [Observation::default(); OBSERVATIONS_LEN],
+            bump: 0,
+            reserved: [0; RESERVED_SPACE]
+        };
+        observations.observations[0].timestamp = now;
+        observations
+    }
+
+    #[test]
+    fn frequent_trades_keep_twap_history() {
+        let mut observations = observations(1);
+        for now in 2..=10_000 {
+            observations.record(now, 1000, 1).unwrap();
+        }
+
+        let span = OBSERVATION_INTERVAL * (OBSERVATIONS_LEN as i64 - 2);
+        assert_eq!(observations.twap(10_000, 1000, span).unwrap(), 1000);
+        assert!(observations.twap(10_000, 1000, 10_000 - 1).is_err());
+    }
+
+    // A fresh pool with 5% fees to each side
+    fn pool(quote_mint: Option<Pubkey>) -> Pool {
+        Pool {
+            version: CURRENT_VERSION,
+            admin: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            curve: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            quote_mint,
+            creator_id: "creator".to_owned(),
+            creator_fee: 500,
+            banger_fee: 500,
+            total_creator_fees: 0,
+            total_banger_fees: 0,
+            verified: false,
+            launch: LaunchSettings::default(),
+            launch_slot: 0,
+            stats: PoolStats::default(),
+            staking_share: 0,
+            total_staked: 0,
+            stakers: 0,
+            reward_per_share: 0,
+            holder_tiers: vec![],
+            paused: 0,
+            bump: 0,
+            authority_bump: 0,
+            reserved: [0; RESERVED_SPACE]
+        }
+    }
+
+    #[test]
+    fn quote_pools_pay_in_quote_base_units() {
+        let sol = pool(None);
+        assert_eq!(sol.to_quote(WHOLE, None, false).unwrap(), WHOLE);
+
+        // 6 decimals, like USDC
+        let quote = pool(Some(Pubkey::new_unique()));
+        assert_eq!(quote.to_quote(WHOLE, Some(6), false).unwrap(), 1_000_000);
+        assert_eq!(quote.to_quote(1, Some(6), false).unwrap(), 0);
+        assert_eq!(quote.to_quote(1, Some(6), true).unwrap(), 1);
+        assert_eq!(quote.to_quote(WHOLE, Some(12), false).unwrap(), 1_000_000_000_000);
+        assert!(quote.to_quote(WHOLE, None, false).is_err());
+    }
+
+    #[test]
+    fn vesting_unlocks_linearly_after_cliff() {
+        let mut schedule = VestingSchedule {
+            version: CURRENT_VERSION,
+            beneficiary: Pubkey::new_unique(),
+            funder: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            total: 1000,
+            claimed: 0,
+            start: 1000,
+            cliff: 1100,
+            duration: 1000,
+            revoked: false,
+            bump: 0,
+            reserved: [0; RESERVED_SPACE]
+        };
+
+        assert_eq!(schedule.vested_amount(0).unwrap(), 0);
+        assert_eq!(schedule.vested_amount(1099).unwrap(), 0);
+        assert_eq!(schedule.vested_amount(1100).unwrap(), 100);
+        assert_eq!(schedule.vested_amount(1500).unwrap(), 500);
+        assert_eq!(schedule.vested_amount(2000).unwrap(), 1000);
+        assert_eq!(schedule.vested_amount(i64::MAX).unwrap(), 1000);
+
+        // Revoked at 1500, with `total` cut down to what had vested
+        schedule.total = 500;
+        schedule.revoked = true;
+        assert_eq!(schedule.vested_amount(1200).unwrap(), 500);
+    }
+
+    #[test]
+    fn twap_weights_prices_by_time() {
+        let mut observations = observations(0);
+        for now in (60..=600).step_by(60) {
+            observations.record(now, 1000, 1).unwrap();
+        }
+        observations.record(660, 3000, 1).unwrap();
+
+        // Spot price until now
+        assert_eq!(observations.twap(720, 3000, 60).unwrap(), 3000);
+        // 120s at 1000, then 120s at 3000
+        assert_eq!(observations.twap(720, 3000, 240).unwrap(), 2000);
+        // Starting between two observations, 150s at 1000
+        assert_eq!(observations.twap(720, 3000, 270).unwrap(), 1888);
+        assert!(observations.twap(720, 3000, 0).is_err());
+    }
+
+    #[test]
+    fn stats_bucket_volume_by_hour() {
+        let mut stats = PoolStats::default();
+        stats.record_trade(10 * 3600, 100).unwrap();
+        stats.record_trade(10 * 3600 + 3599, 50).unwrap();
+        stats.record_trade(11 * 3600, 30).unwrap();
+        assert_eq!(stats.hourly_volume[10], 150);
+        assert_eq!(stats.hourly_volume[11], 30);
+
+        // A day later the old buckets are cleared before reuse
+        stats.record_trade(35 * 3600, 7).unwrap();
+        assert_eq!(stats.hourly_volume[10], 0);
+        assert_eq!(stats.hourly_volume[11], 7);
+        assert_eq!(stats.last_hour, 35);
+        assert_eq!(stats.hourly_volume.iter().sum::<u64>(), 7);
+
+        assert_eq!(stats.volume, 187);
+        assert_eq!(stats.trade_count, 4);
+    }
+
+    fn stake() -> Stake {
+        Stake {
+            version: CURRENT_VERSION,
+            owner: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            amount: 0,
+            reward_debt: 0,
+            pending: 0,
+            claimed: 0,
+            staked_at: 0,
+            bump: 0,
+            reserved: [0; RESERVED_SPACE]
+        }
+    }
+
+    #[test]
+    fn rewards_split_by_stake_while_staked() {
+        let mut pool = pool(None);
+        assert!(!pool.distribute_rewards(100).unwrap());
+
+        let mut early = stake();
+        early.set_amount(300, pool.reward_per_share).unwrap();
+        pool.total_staked = 300;
+        assert!(pool.distribute_rewards(300).unwrap());
+
+        // Joining late earns nothing from earlier rewards
+        let mut late = stake();
+        late.set_amount(100, pool.reward_per_share).unwrap();
+        pool.total_staked = 400;
+        assert!(pool.distribute_rewards(400).unwrap());
+
+        early.settle(pool.reward_per_share).unwrap();
+        late.settle(pool.reward_per_share).unwrap();
+        assert_eq!(early.pending, 600);
+        assert_eq!(late.pending, 100);
+
+        // Settling twice doesn't pay twice, unstaking keeps what was earned
+        early.settle(pool.reward_per_share).unwrap();
+        early.set_amount(0, pool.reward_per_share).unwrap();
+        assert_eq!(early.pending, 600);
+        assert!(!early.is_empty());
+
+        early.pending = 0;
+        assert!(early.is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+use std::{env, path::PathBuf};
+
+// The suite runs against the dumped Token Metadata binary, the stub has to be asked for
+fn main() {
+    println!("cargo:rerun-if-changed=tests/fixtures");
+    println!("cargo:rerun-if-env-changed=SBF_OUT_DIR");
+    println!("cargo:rerun-if-env-changed=BPF_OUT_DIR");
+
+    let dirs = ["BPF_OUT_DIR", "SBF_OUT_DIR"].into_iter()
+        .filter_map(|var| env::var_os(var).map(PathBuf::from))
+        .chain([PathBuf::from("tests/fixtures")]);
+    let binary = dirs.map(|dir| dir.join("mpl_token_metadata.so")).any(|file| file.is_file());
+    let stub = env::var_os("CARGO_FEATURE_METADATA_STUB").is_some();
+    if !binary && !stub {
+        panic!(
+            "Token Metadata binary not found in tests/fixtures. Run tests/integration/fetch-fixtures.sh, \
+             or build with --features metadata-stub to use the native stand-in"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+#!/usr/bin/env sh
+# Dumps the mainnet Token Metadata program into tests/fixtures, where the
+# integration tests load it from. Needs the Solana CLI and network access.
+set -eu
+
+cd "$(dirname "$0")"
+solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s tests/fixtures/mpl_token_metadata.so
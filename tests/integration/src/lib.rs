@@ -0,0 +1,1283 @@
+//! Offline test harness for banger-program.
+//!
+//! Runs the compiled program and the mainnet Token Metadata binary in a
+//! `solana-program-test` bank. The program is loaded from `target/deploy`
+//! after `anchor build`, or from `SBF_OUT_DIR`, and runs natively when neither
+//! has it. Token Metadata is dumped once into `tests/fixtures` with
+//! `fetch-fixtures.sh`, which runs
+//!
+//! ```text
+//! solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s tests/fixtures/mpl_token_metadata.so
+//! ```
+//!
+//! Without it the build fails. The native stand-in in `src/token_metadata.rs`
+//! can be enabled instead with `--features metadata-stub`, but it only does the
+//! token side of each call, so the dumped binary is what the suite is meant to
+//! run against.
+
+#[cfg(feature = "metadata-stub")]
+pub mod token_metadata;
+
+use anchor_lang::{
+    prelude::{AccountInfo, Pubkey},
+    solana_program::{
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack,
+        rent::Rent,
+        system_program,
+        sysvar
+    },
+    AccountDeserialize,
+    AnchorSerialize,
+    InstructionData,
+    ToAccountMetas
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::{Mint, TokenAccount}};
+use banger_program::{BasketLeg, HolderBadge, LaunchSettings, OrderSide, Pool, Position, ReclaimTarget, TreasuryShare, VaultExpiry};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext};
+use solana_sdk::{account::Account, signature::Keypair, signer::Signer, transaction::Transaction};
+use std::path::{Path, PathBuf};
+
+pub const CURVE_POW: u64 = 2;
+pub const CURVE_FRAC: u64 = 32000;
+pub const CREATOR_ID: &str = "creator";
+pub const CREATOR_FEE: u16 = 500;
+pub const BANGER_FEE: u16 = 500;
+pub const DECIMALS: u8 = 6;
+/// Same as SOL, so quote pool amounts match the lamport amounts of SOL pools.
+pub const QUOTE_DECIMALS: u8 = 9;
+
+// Anchor ties the account infos to the lifetime of the entrypoint
+fn banger_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    banger_program::entry(program_id, accounts, data)
+}
+
+// `anchor build` output, or wherever `SBF_OUT_DIR` points as with `cargo test-sbf`
+fn banger_binary() -> Option<Vec<u8>> {
+    let dir = ["BPF_OUT_DIR", "SBF_OUT_DIR"].into_iter()
+        .find_map(std::env::var_os)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"));
+    std::fs::read(dir.join("banger_program.so")).ok()
+}
+
+/// The bank with both programs, the binary deploy of banger-program also needs
+/// `program_data_account` at `program_data()`.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::default();
+    if banger_binary().is_some() {
+        let state = UpgradeableLoaderState::Program { programdata_address: program_data() };
+        let data = bincode::serialize(&state).unwrap();
+        program_test.add_account(banger_program::ID, Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bpf_loader_upgradeable::ID,
+            executable: true,
+            rent_epoch: 0
+        });
+    } else {
+        program_test.add_program("banger_program", banger_program::ID, processor!(banger_entry));
+    }
+    add_token_metadata(&mut program_test);
+    program_test
+}
+
+#[cfg(feature = "metadata-stub")]
+fn add_token_metadata(program_test: &mut ProgramTest) {
+    program_test.add_program(
+        "mpl_token_metadata",
+        mpl_token_metadata::ID,
+        processor!(token_metadata::process_instruction)
+    );
+}
+
+// build.rs already checked the binary is there
+#[cfg(not(feature = "metadata-stub"))]
+fn add_token_metadata(program_test: &mut ProgramTest) {
+    let file = solana_program_test::find_file("mpl_token_metadata.so")
+        .expect("Token Metadata binary, run fetch-fixtures.sh");
+    let data = solana_program_test::read_file(file);
+    program_test.add_account(mpl_token_metadata::ID, Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: anchor_lang::solana_program::bpf_loader::ID,
+        executable: true,
+        rent_epoch: 0
+    });
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &banger_program::ID).0
+}
+
+pub fn config() -> Pubkey {
+    pda(&[b"config"])
+}
+
+pub fn curve() -> Pubkey {
+    pda(&[b"curve"])
+}
+
+pub fn authority() -> Pubkey {
+    pda(&[b"authority"])
+}
+
+pub fn treasury() -> Pubkey {
+    pda(&[b"treasury"])
+}
+
+pub fn creator_profile() -> Pubkey {
+    creator_profile_of(CREATOR_ID)
+}
+
+pub fn creator_profile_of(creator_id: &str) -> Pubkey {
+    pda(&[b"creator", creator_id.as_bytes()])
+}
+
+pub fn creator_vault() -> Pubkey {
+    creator_vault_of(CREATOR_ID)
+}
+
+pub fn creator_vault_of(creator_id: &str) -> Pubkey {
+    pda(&[b"creator_vault", creator_id.as_bytes()])
+}
+
+pub fn pool(mint: &Pubkey) -> Pubkey {
+    pda(&[b"pool", mint.as_ref()])
+}
+
+pub fn observations(pool: &Pubkey) -> Pubkey {
+    pda(&[b"observations", pool.as_ref()])
+}
+
+pub fn position(pool: &Pubkey, owner: &Pubkey) -> Pubkey {
+    pda(&[b"position", pool.as_ref(), owner.as_ref()])
+}
+
+pub fn order(pool: &Pubkey, owner: &Pubkey, id: u64) -> Pubkey {
+    pda(&[b"order", pool.as_ref(), owner.as_ref(), &id.to_le_bytes()])
+}
+
+pub fn order_escrow(order: &Pubkey) -> Pubkey {
+    pda(&[b"order_escrow", order.as_ref()])
+}
+
+pub fn dca_plan(pool: &Pubkey, owner: &Pubkey) -> Pubkey {
+    pda(&[b"dca", pool.as_ref(), owner.as_ref()])
+}
+
+pub fn vesting(mint: &Pubkey, beneficiary: &Pubkey, funder: &Pubkey) -> Pubkey {
+    pda(&[b"vesting", mint.as_ref(), beneficiary.as_ref(), funder.as_ref()])
+}
+
+pub fn vesting_escrow(vesting: &Pubkey) -> Pubkey {
+    pda(&[b"vesting_escrow", vesting.as_ref()])
+}
+
+pub fn stake(pool: &Pubkey, owner: &Pubkey) -> Pubkey {
+    pda(&[b"stake", pool.as_ref(), owner.as_ref()])
+}
+
+pub fn stake_vault(pool: &Pubkey) -> Pubkey {
+    pda(&[b"stake_vault", pool.as_ref()])
+}
+
+pub fn holder_badge(pool: &Pubkey, holder: &Pubkey) -> Pubkey {
+    pda(&[b"badge", pool.as_ref(), holder.as_ref()])
+}
+
+pub fn quote_reserve(pool: &Pubkey) -> Pubkey {
+    pda(&[b"quote_reserve", pool.as_ref()])
+}
+
+pub fn metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID
+    ).0
+}
+
+pub fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: banger_program::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data()
+    }
+}
+
+pub fn program_data() -> Pubkey {
+    Pubkey::find_program_address(&[banger_program::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+// What the upgradeable loader keeps for a deployed program, with the bytecode when there is a binary
+pub fn program_data_account(upgrade_authority: &Pubkey) -> Account {
+    let state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*upgrade_authority)
+    };
+    let mut data = bincode::serialize(&state).unwrap();
+    data.extend(banger_binary().unwrap_or_default());
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: bpf_loader_upgradeable::ID,
+        ..Account::default()
+    }
+}
+
+/// A pool as created before accounts were versioned, at `pool(mint)`.
+pub fn legacy_pool_account(admin: &Pubkey, mint: &Pubkey, creator_id: &str) -> Account {
+    // Anchor discriminator followed by the fixed-size legacy layout
+    const SPACE: usize = 8 + 32 * 4 + (4 + 32) + 2 * 2 + 2;
+
+    let (_, bump) = Pubkey::find_program_address(&[b"pool", mint.as_ref()], &banger_program::ID);
+    let (_, authority_bump) = Pubkey::find_program_address(&[b"authority"], &banger_program::ID);
+
+    // Treasury fees went to a wallet back then, the admin's here
+    let mut data = hash(b"account:Pool").to_bytes()[..8].to_vec();
+    (*admin, *mint, curve(), *admin, creator_id.to_owned(), CREATOR_FEE, BANGER_FEE, bump, authority_bump)
+        .serialize(&mut data)
+        .unwrap();
+    data.resize(SPACE, 0);
+
+    Account {
+        lamports: Rent::default().minimum_balance(SPACE),
+        data,
+        owner: banger_program::ID,
+        ..Account::default()
+    }
+}
+
+pub fn init_config(admin: &Pubkey) -> Instruction {
+    instruction(
+        banger_program::accounts::InitConfig {
+            admin: *admin,
+            config: config(),
+            program_data: program_data(),
+            treasury: treasury(),
+            system_program: system_program::ID
+        },
+        banger_program::instruction::InitConfig {}
+    )
+}
+
+fn pool_accounts(payer: &Pubkey, mint: &Pubkey, creator_id: &str, previous_pool: Option<Pubkey>, quote_mint: Option<Pubkey>) -> banger_program::accounts::InitPool {
+    let pool = pool(mint);
+    banger_program::accounts::InitPool {
+        admin: *payer,
+        mint: *mint,
+        authority: authority(),
+        metadata: metadata(mint),
+        curve: curve(),
+        pool,
+        observations: observations(&pool),
+        creator_profile: creator_profile_of(creator_id),
+        previous_pool,
+        treasury: treasury(),
+        creator_vault: creator_vault_of(creator_id),
+        quote_mint,
+        quote_reserve: quote_mint.map(|_| quote_reserve(&pool)),
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        metadata_program: mpl_token_metadata::ID,
+        sysvar_instructions: sysvar::instructions::ID
+    }
+}
+
+/// A SOL pool for `creator_id`, signed by `payer` and `mint`.
+pub fn init_pool(payer: &Pubkey, mint: &Pubkey, creator_id: &str, launch: LaunchSettings) -> Instruction {
+    init_quote_pool(payer, mint, creator_id, launch, None)
+}
+
+/// Same as [`init_pool`], priced in `quote_mint` if given.
+pub fn init_quote_pool(payer: &Pubkey, mint: &Pubkey, creator_id: &str, launch: LaunchSettings, quote_mint: Option<Pubkey>) -> Instruction {
+    instruction(
+        pool_accounts(payer, mint, creator_id, None, quote_mint),
+        banger_program::instruction::InitPool {
+            creator_id: creator_id.to_owned(),
+            creator_fee: CREATOR_FEE,
+            banger_fee: BANGER_FEE,
+            decimals: DECIMALS,
+            token_name: "Test".to_owned(),
+            token_metadata_uri: "https://example.com/test.json".to_owned(),
+            launch
+        }
+    )
+}
+
+/// Same as [`init_pool`] from the creator's linked wallet, replacing `previous_pool` if given.
+pub fn launch_creator_pool(creator: &Pubkey, mint: &Pubkey, previous_pool: Option<Pubkey>) -> Instruction {
+    instruction(
+        pool_accounts(creator, mint, CREATOR_ID, previous_pool, None),
+        banger_program::instruction::LaunchCreatorPool {
+            creator_id: CREATOR_ID.to_owned(),
+            creator_fee: CREATOR_FEE,
+            banger_fee: BANGER_FEE,
+            decimals: DECIMALS,
+            token_name: "Creator".to_owned(),
+            token_metadata_uri: "https://example.com/creator.json".to_owned(),
+            launch: LaunchSettings::default()
+        }
+    )
+}
+
+/// A premint of `amount` for the creator of the pool of `mint`, liquid when
+/// `vesting_duration` is 0, paid in `quote_mint` if the pool is priced in it.
+pub fn reserve_creator_allocation(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    amount_in: u64,
+    vesting_duration: i64,
+    quote_mint: Option<Pubkey>
+) -> Instruction {
+    let pool = pool(mint);
+    let vesting = (vesting_duration > 0).then(|| vesting(mint, creator, creator));
+    instruction(
+        banger_program::accounts::ReserveCreatorAllocation {
+            creator: *creator,
+            creator_profile: creator_profile(),
+            mint: *mint,
+            vesting,
+            vesting_escrow: vesting.as_ref().map(vesting_escrow),
+            creator_ata: vesting.is_none().then(|| get_associated_token_address(creator, mint)),
+            authority: authority(),
+            metadata: metadata(mint),
+            curve: curve(),
+            treasury: treasury(),
+            creator_vault: creator_vault(),
+            pool,
+            position: position(&pool, creator),
+            observations: observations(&pool),
+            config: config(),
+            quote_mint,
+            creator_quote_ata: quote_mint.map(|q| get_associated_token_address(creator, &q)),
+            quote_reserve: quote_mint.map(|_| quote_reserve(&pool)),
+            treasury_quote_ata: quote_mint.map(|q| get_associated_token_address(&treasury(), &q)),
+            creator_vault_quote_ata: quote_mint.map(|q| get_associated_token_address(&creator_vault(), &q)),
+            system_program: system_program::ID,
+            metadata_program: mpl_token_metadata::ID,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            sysvar_instructions: sysvar::instructions::ID
+        },
+        banger_program::instruction::ReserveCreatorAllocation {
+            amount,
+            amount_in,
+            vesting_duration
+        }
+    )
+}
+
+/// A bank with the config and curve set up and one pool launched for `CREATOR_ID`.
+pub struct Env {
+    pub ctx: ProgramTestContext,
+    /// Upgrade authority, and so config admin holding every role.
+    pub admin: Keypair,
+    pub mint: Pubkey,
+    /// Set when the pool is priced in an SPL token, minted by the payer.
+    pub quote_mint: Option<Pubkey>
+}
+
+impl Env {
+    pub async fn start() -> Self {
+        Self::start_with(LaunchSettings::default()).await
+    }
+
+    /// Same as [`Env::start`] with the pool launched under `launch`.
+    pub async fn start_with(launch: LaunchSettings) -> Self {
+        Self::launch(launch, None).await
+    }
+
+    /// Same as [`Env::start`] with the pool priced in a fresh SPL token of `QUOTE_DECIMALS`.
+    pub async fn start_quoted() -> Self {
+        Self::launch(LaunchSettings::default(), Some(QUOTE_DECIMALS)).await
+    }
+
+    async fn launch(launch: LaunchSettings, quote_decimals: Option<u8>) -> Self {
+        let admin = Keypair::new();
+
+        let mut program_test = program_test();
+        program_test.add_account(admin.pubkey(), Account {
+            lamports: 100 * LAMPORTS_PER_SOL,
+            ..Account::default()
+        });
+        program_test.add_account(program_data(), program_data_account(&admin.pubkey()));
+
+        let mut ctx = program_test.start_with_context().await;
+
+        let init_config = init_config(&admin.pubkey());
+        let init_curve = instruction(
+            banger_program::accounts::InitCurve {
+                admin: admin.pubkey(),
+                curve: curve(),
+                system_program: system_program::ID
+            },
+            banger_program::instruction::InitCurve { pow: CURVE_POW, frac: CURVE_FRAC }
+        );
+        send(&mut ctx, &[init_config, init_curve], &[&admin]).await.unwrap();
+
+        let quote_mint = match quote_decimals {
+            Some(decimals) => Some(create_mint(&mut ctx, decimals).await),
+            None => None
+        };
+
+        let mint = Keypair::new();
+        let init_pool = init_quote_pool(&admin.pubkey(), &mint.pubkey(), CREATOR_ID, launch, quote_mint);
+        send(&mut ctx, &[init_pool], &[&admin, &mint]).await.unwrap();
+
+        Env { ctx, admin, mint: mint.pubkey(), quote_mint }
+    }
+
+    pub fn pool(&self) -> Pubkey {
+        pool(&self.mint)
+    }
+
+    /// Launches a second SOL pool for `creator_id` from the admin, returning its mint.
+    pub async fn add_pool(&mut self, creator_id: &str) -> Pubkey {
+        let mint = Keypair::new();
+        let ix = init_pool(&self.admin.pubkey(), &mint.pubkey(), creator_id, LaunchSettings::default());
+        send(&mut self.ctx, &[ix], &[&self.admin, &mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// Funds a fresh wallet from the payer.
+    pub async fn wallet(&mut self, lamports: u64) -> Keypair {
+        let wallet = Keypair::new();
+        let transfer = anchor_lang::solana_program::system_instruction::transfer(
+            &self.ctx.payer.pubkey(),
+            &wallet.pubkey(),
+            lamports
+        );
+        send(&mut self.ctx, &[transfer], &[]).await.unwrap();
+        wallet
+    }
+
+    pub async fn buy(&mut self, buyer: &Keypair, amount_in: u64, num_mint: u64) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::Buy {
+                buyer: buyer.pubkey(),
+                mint: self.mint,
+                buyer_ata: get_associated_token_address(&buyer.pubkey(), &self.mint),
+                authority: authority(),
+                metadata: metadata(&self.mint),
+                curve: curve(),
+                treasury: treasury(),
+                creator_vault: creator_vault(),
+                pool,
+                position: position(&pool, &buyer.pubkey()),
+                observations: observations(&pool),
+                config: config(),
+                referral: None,
+                quote_mint: self.quote_mint,
+                buyer_quote_ata: self.quote_mint.map(|q| get_associated_token_address(&buyer.pubkey(), &q)),
+                quote_reserve: self.quote_mint.map(|_| quote_reserve(&pool)),
+                treasury_quote_ata: self.quote_mint.map(|q| get_associated_token_address(&treasury(), &q)),
+                creator_vault_quote_ata: self.quote_mint.map(|q| get_associated_token_address(&creator_vault(), &q)),
+                system_program: system_program::ID,
+                metadata_program: mpl_token_metadata::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                sysvar_instructions: sysvar::instructions::ID
+            },
+            banger_program::instruction::Buy { amount_in, num_mint }
+        );
+        send(&mut self.ctx, &[ix], &[buyer]).await
+    }
+
+    /// A one-leg basket buy on the env's pool.
+    pub async fn buy_basket(&mut self, buyer: &Keypair, amount_in: u64, num_mint: u64) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let mut ix = instruction(
+            banger_program::accounts::BuyBasket {
+                buyer: buyer.pubkey(),
+                authority: authority(),
+                curve: curve(),
+                treasury: treasury(),
+                config: config(),
+                referral: None,
+                system_program: system_program::ID,
+                metadata_program: mpl_token_metadata::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                sysvar_instructions: sysvar::instructions::ID
+            },
+            banger_program::instruction::BuyBasket {
+                legs: vec![BasketLeg { num_mint, amount_in }],
+                amount_in
+            }
+        );
+        ix.accounts.extend([
+            AccountMeta::new(pool, false),
+            AccountMeta::new(self.mint, false),
+            AccountMeta::new(metadata(&self.mint), false),
+            AccountMeta::new(creator_vault(), false),
+            AccountMeta::new(get_associated_token_address(&buyer.pubkey(), &self.mint), false),
+            AccountMeta::new(position(&pool, &buyer.pubkey()), false),
+            AccountMeta::new(observations(&pool), false)
+        ]);
+        send(&mut self.ctx, &[ix], &[buyer]).await
+    }
+
+    pub async fn sell(&mut self, seller: &Keypair, num_burn: u64, amount_out: u64) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::Sell {
+                seller: seller.pubkey(),
+                mint: self.mint,
+                seller_ata: get_associated_token_address(&seller.pubkey(), &self.mint),
+                authority: authority(),
+                metadata: metadata(&self.mint),
+                curve: curve(),
+                treasury: treasury(),
+                creator_vault: creator_vault(),
+                pool,
+                position: position(&pool, &seller.pubkey()),
+                observations: observations(&pool),
+                config: config(),
+                referral: None,
+                quote_mint: self.quote_mint,
+                seller_quote_ata: self.quote_mint.map(|q| get_associated_token_address(&seller.pubkey(), &q)),
+                quote_reserve: self.quote_mint.map(|_| quote_reserve(&pool)),
+                treasury_quote_ata: self.quote_mint.map(|q| get_associated_token_address(&treasury(), &q)),
+                creator_vault_quote_ata: self.quote_mint.map(|q| get_associated_token_address(&creator_vault(), &q)),
+                system_program: system_program::ID,
+                metadata_program: mpl_token_metadata::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                sysvar_instructions: sysvar::instructions::ID
+            },
+            banger_program::instruction::Sell { num_burn, amount_out }
+        );
+        send(&mut self.ctx, &[ix], &[seller]).await
+    }
+
+    /// Sells `num_burn` tokens of the env's pool into the pool of `mint_b`.
+    pub async fn swap(&mut self, trader: &Keypair, mint_b: &Pubkey, num_burn: u64, min_out: u64) -> Result<(), BanksClientError> {
+        let pool_a = self.pool();
+        let pool_b = pool(mint_b);
+        let creator_id_b = self.pool_state(&pool_b).await.creator_id;
+        let ix = instruction(
+            banger_program::accounts::SwapCreatorTokens {
+                trader: trader.pubkey(),
+                mint_a: self.mint,
+                trader_ata_a: get_associated_token_address(&trader.pubkey(), &self.mint),
+                metadata_a: metadata(&self.mint),
+                creator_vault_a: creator_vault(),
+                pool_a,
+                position_a: position(&pool_a, &trader.pubkey()),
+                observations_a: observations(&pool_a),
+                mint_b: *mint_b,
+                trader_ata_b: get_associated_token_address(&trader.pubkey(), mint_b),
+                metadata_b: metadata(mint_b),
+                pool_b,
+                creator_vault_b: creator_vault_of(&creator_id_b),
+                position_b: position(&pool_b, &trader.pubkey()),
+                observations_b: observations(&pool_b),
+                authority: authority(),
+                curve: curve(),
+                treasury: treasury(),
+                config: config(),
+                system_program: system_program::ID,
+                metadata_program: mpl_token_metadata::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                sysvar_instructions: sysvar::instructions::ID
+            },
+            banger_program::instruction::SwapCreatorTokens { num_burn, min_out }
+        );
+        send(&mut self.ctx, &[ix], &[trader]).await
+    }
+
+    /// Places order `id` without a keeper tip, escrowing the tokens of a sell order.
+    pub async fn place_order(
+        &mut self,
+        owner: &Keypair,
+        id: u64,
+        side: OrderSide,
+        amount: u64,
+        trigger_price: u64,
+        limit: u64
+    ) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let order = order(&pool, &owner.pubkey(), id);
+        let ix = instruction(
+            banger_program::accounts::PlaceOrder {
+                owner: owner.pubkey(),
+                mint: self.mint,
+                pool,
+                order,
+                order_escrow: (side == OrderSide::Sell).then(|| order_escrow(&order)),
+                owner_ata: get_associated_token_address(&owner.pubkey(), &self.mint),
+                position: position(&pool, &owner.pubkey()),
+                authority: authority(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::PlaceOrder { id, side, amount, trigger_price, limit, keeper_tip: 0 }
+        );
+        send(&mut self.ctx, &[ix], &[owner]).await
+    }
+
+    pub async fn cancel_order(&mut self, owner: &Keypair, id: u64, with_escrow: bool) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let order = order(&pool, &owner.pubkey(), id);
+        let ix = instruction(
+            banger_program::accounts::CancelOrder {
+                owner: owner.pubkey(),
+                mint: self.mint,
+                order,
+                order_escrow: with_escrow.then(|| order_escrow(&order)),
+                owner_ata: with_escrow.then(|| get_associated_token_address(&owner.pubkey(), &self.mint)),
+                authority: authority(),
+                token_program: spl_token::ID
+            },
+            banger_program::instruction::CancelOrder {}
+        );
+        send(&mut self.ctx, &[ix], &[owner]).await
+    }
+
+    /// Fills order `id` of `owner` from `keeper`, passing the escrow of a sell order.
+    pub async fn execute_order(&mut self, keeper: &Keypair, owner: &Pubkey, id: u64, side: OrderSide) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let order = order(&pool, owner, id);
+        let ix = instruction(
+            banger_program::accounts::ExecuteOrder {
+                keeper: keeper.pubkey(),
+                owner: *owner,
+                order,
+                order_escrow: (side == OrderSide::Sell).then(|| order_escrow(&order)),
+                owner_ata: get_associated_token_address(owner, &self.mint),
+                position: position(&pool, owner),
+                mint: self.mint,
+                authority: authority(),
+                metadata: metadata(&self.mint),
+                curve: curve(),
+                treasury: treasury(),
+                creator_vault: creator_vault(),
+                pool,
+                observations: observations(&pool),
+                config: config(),
+                system_program: system_program::ID,
+                metadata_program: mpl_token_metadata::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                sysvar_instructions: sysvar::instructions::ID
+            },
+            banger_program::instruction::ExecuteOrder {}
+        );
+        send(&mut self.ctx, &[ix], &[keeper]).await
+    }
+
+    pub async fn open_dca(
+        &mut self,
+        owner: &Keypair,
+        lamports_per_interval: u64,
+        interval: i64,
+        max_price: u64,
+        deposit: u64
+    ) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::OpenDca {
+                owner: owner.pubkey(),
+                mint: self.mint,
+                pool,
+                dca_plan: dca_plan(&pool, &owner.pubkey()),
+                owner_ata: get_associated_token_address(&owner.pubkey(), &self.mint),
+                position: position(&pool, &owner.pubkey()),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::OpenDca { lamports_per_interval, interval, max_price, deposit }
+        );
+        send(&mut self.ctx, &[ix], &[owner]).await
+    }
+
+    pub async fn execute_dca(&mut self, cranker: &Keypair, owner: &Pubkey) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::ExecuteDca {
+                cranker: cranker.pubkey(),
+                owner: *owner,
+                dca_plan: dca_plan(&pool, owner),
+                owner_ata: get_associated_token_address(owner, &self.mint),
+                position: position(&pool, owner),
+                mint: self.mint,
+                authority: authority(),
+                metadata: metadata(&self.mint),
+                curve: curve(),
+                treasury: treasury(),
+                creator_vault: creator_vault(),
+                pool,
+                observations: observations(&pool),
+                config: config(),
+                system_program: system_program::ID,
+                metadata_program: mpl_token_metadata::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                sysvar_instructions: sysvar::instructions::ID
+            },
+            banger_program::instruction::ExecuteDca {}
+        );
+        send(&mut self.ctx, &[ix], &[cranker]).await
+    }
+
+    pub async fn close_dca(&mut self, owner: &Keypair) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::CloseDca {
+                owner: owner.pubkey(),
+                dca_plan: dca_plan(&self.pool(), &owner.pubkey())
+            },
+            banger_program::instruction::CloseDca {}
+        );
+        send(&mut self.ctx, &[ix], &[owner]).await
+    }
+
+    /// Escrows `total` of the env's token from `funder` for `beneficiary`.
+    pub async fn create_vesting(
+        &mut self,
+        funder: &Keypair,
+        beneficiary: &Pubkey,
+        total: u64,
+        start: i64,
+        cliff: i64,
+        duration: i64
+    ) -> Result<(), BanksClientError> {
+        let vesting = vesting(&self.mint, beneficiary, &funder.pubkey());
+        let ix = instruction(
+            banger_program::accounts::CreateVesting {
+                funder: funder.pubkey(),
+                mint: self.mint,
+                funder_ata: get_associated_token_address(&funder.pubkey(), &self.mint),
+                vesting,
+                vesting_escrow: vesting_escrow(&vesting),
+                authority: authority(),
+                pool: self.pool(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID
+            },
+            banger_program::instruction::CreateVesting { beneficiary: *beneficiary, total, start, cliff, duration }
+        );
+        send(&mut self.ctx, &[ix], &[funder]).await
+    }
+
+    pub async fn claim_vested(&mut self, beneficiary: &Keypair, funder: &Pubkey) -> Result<(), BanksClientError> {
+        let vesting = vesting(&self.mint, &beneficiary.pubkey(), funder);
+        let ix = instruction(
+            banger_program::accounts::ClaimVested {
+                beneficiary: beneficiary.pubkey(),
+                mint: self.mint,
+                vesting,
+                vesting_escrow: vesting_escrow(&vesting),
+                beneficiary_ata: get_associated_token_address(&beneficiary.pubkey(), &self.mint),
+                authority: authority(),
+                config: config(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::ClaimVested {}
+        );
+        send(&mut self.ctx, &[ix], &[beneficiary]).await
+    }
+
+    /// Returns what hasn't vested yet to the funder, signed by the admin.
+    pub async fn revoke_unvested(&mut self, beneficiary: &Pubkey, funder: &Pubkey) -> Result<(), BanksClientError> {
+        let vesting = vesting(&self.mint, beneficiary, funder);
+        let ix = instruction(
+            banger_program::accounts::RevokeUnvested {
+                admin: self.admin.pubkey(),
+                config: config(),
+                mint: self.mint,
+                vesting,
+                vesting_escrow: vesting_escrow(&vesting),
+                funder_ata: get_associated_token_address(funder, &self.mint),
+                authority: authority(),
+                token_program: spl_token::ID
+            },
+            banger_program::instruction::RevokeUnvested {}
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    /// Tips `CREATOR_ID` in SOL, or in `mint` from the tipper's ATA if given.
+    pub async fn tip_creator(&mut self, tipper: &Keypair, amount: u64, mint: Option<Pubkey>) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::TipCreator {
+                tipper: tipper.pubkey(),
+                creator_profile: creator_profile(),
+                creator_vault: creator_vault(),
+                mint,
+                tipper_ata: mint.map(|m| get_associated_token_address(&tipper.pubkey(), &m)),
+                vault_ata: mint.map(|m| get_associated_token_address(&creator_vault(), &m)),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::TipCreator {
+                creator_id: CREATOR_ID.to_owned(),
+                amount,
+                memo: Some("gm".to_owned())
+            }
+        );
+        send(&mut self.ctx, &[ix], &[tipper]).await
+    }
+
+    pub async fn set_launch_settings(&mut self, creator: &Keypair, launch: LaunchSettings) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::SetLaunchSettings {
+                creator: creator.pubkey(),
+                creator_profile: creator_profile(),
+                mint: self.mint,
+                pool: self.pool()
+            },
+            banger_program::instruction::SetLaunchSettings { launch }
+        );
+        send(&mut self.ctx, &[ix], &[creator]).await
+    }
+
+    pub async fn set_holder_tiers(&mut self, creator: &Keypair, tiers: Vec<u64>) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::SetHolderTiers {
+                creator: creator.pubkey(),
+                creator_profile: creator_profile(),
+                pool: self.pool()
+            },
+            banger_program::instruction::SetHolderTiers { tiers }
+        );
+        send(&mut self.ctx, &[ix], &[creator]).await
+    }
+
+    pub async fn set_staking_share(&mut self, creator: &Keypair, staking_share: u16) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::SetStakingShare {
+                creator: creator.pubkey(),
+                creator_profile: creator_profile(),
+                pool: self.pool()
+            },
+            banger_program::instruction::SetStakingShare { staking_share }
+        );
+        send(&mut self.ctx, &[ix], &[creator]).await
+    }
+
+    pub async fn stake(&mut self, owner: &Keypair, amount: u64) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::StakeTokens {
+                owner: owner.pubkey(),
+                mint: self.mint,
+                pool,
+                stake: stake(&pool, &owner.pubkey()),
+                stake_vault: stake_vault(&pool),
+                owner_ata: get_associated_token_address(&owner.pubkey(), &self.mint),
+                authority: authority(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::Stake { amount }
+        );
+        send(&mut self.ctx, &[ix], &[owner]).await
+    }
+
+    pub async fn unstake(&mut self, owner: &Keypair, amount: u64) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::UnstakeTokens {
+                owner: owner.pubkey(),
+                mint: self.mint,
+                pool,
+                stake: stake(&pool, &owner.pubkey()),
+                stake_vault: stake_vault(&pool),
+                owner_ata: get_associated_token_address(&owner.pubkey(), &self.mint),
+                authority: authority(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::Unstake { amount }
+        );
+        send(&mut self.ctx, &[ix], &[owner]).await
+    }
+
+    pub async fn claim_rewards(&mut self, owner: &Keypair) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::ClaimRewards {
+                owner: owner.pubkey(),
+                pool,
+                stake: stake(&pool, &owner.pubkey()),
+                config: config(),
+                quote_reserve: None,
+                owner_quote_ata: None,
+                token_program: spl_token::ID
+            },
+            banger_program::instruction::ClaimRewards {}
+        );
+        send(&mut self.ctx, &[ix], &[owner]).await
+    }
+
+    /// Refreshes the badge of `holder` on the env's pool, without counting stake.
+    pub async fn verify_holding(&mut self, holder: &Pubkey) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::VerifyHolding {
+                payer: self.ctx.payer.pubkey(),
+                holder: *holder,
+                mint: self.mint,
+                pool,
+                holder_ata: get_associated_token_address(holder, &self.mint),
+                stake: None,
+                holder_badge: holder_badge(&pool, holder),
+                system_program: system_program::ID
+            },
+            banger_program::instruction::VerifyHolding {}
+        );
+        send(&mut self.ctx, &[ix], &[]).await
+    }
+
+    pub async fn holder_badge(&mut self, holder: &Pubkey) -> HolderBadge {
+        let pool = self.pool();
+        self.account(&holder_badge(&pool, holder)).await
+    }
+
+    pub async fn pool_state(&mut self, pool: &Pubkey) -> Pool {
+        self.account(pool).await
+    }
+
+    pub async fn position_state(&mut self, pool: &Pubkey, owner: &Pubkey) -> Position {
+        self.account(&position(pool, owner)).await
+    }
+
+    /// Pauses `flags` on the env's pool, or program wide when `on_pool` is false.
+    pub async fn pause(&mut self, flags: u8, on_pool: bool) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::SetPause {
+                pauser: self.admin.pubkey(),
+                config: config(),
+                pool: on_pool.then(|| self.pool())
+            },
+            banger_program::instruction::Pause { flags }
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    pub async fn unpause(&mut self, flags: u8, on_pool: bool) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::SetPause {
+                pauser: self.admin.pubkey(),
+                config: config(),
+                pool: on_pool.then(|| self.pool())
+            },
+            banger_program::instruction::Unpause { flags }
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    /// Closes the env's pool, refunding the rent to the admin who launched it.
+    pub async fn close_pool(&mut self, closer: &Keypair) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::ClosePool {
+                closer: closer.pubkey(),
+                payer: self.admin.pubkey(),
+                mint: self.mint,
+                pool,
+                observations: observations(&pool),
+                creator_profile: creator_profile(),
+                config: config(),
+                stake_vault: stake_vault(&pool),
+                authority: authority(),
+                quote_mint: self.quote_mint,
+                quote_reserve: self.quote_mint.map(|_| quote_reserve(&pool)),
+                payer_quote_ata: self.quote_mint.map(|q| get_associated_token_address(&self.admin.pubkey(), &q)),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::ClosePool {}
+        );
+        send(&mut self.ctx, &[ix], &[closer]).await
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+    }
+
+    /// Moves the bank to a later slot and the clock `seconds` ahead.
+    pub async fn warp(&mut self, seconds: i64) {
+        let mut clock = self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+        self.ctx.warp_to_slot(clock.slot + 100).unwrap();
+        clock.slot += 100;
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn set_vault_expiry(&mut self, period: i64, target: ReclaimTarget) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::SetVaultExpiry {
+                admin: self.admin.pubkey(),
+                config: config()
+            },
+            banger_program::instruction::SetVaultExpiry {
+                vault_expiry: VaultExpiry { period, target }
+            }
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    /// Sweeps the creator vault, and its ATA for `token_mint` if given, from the payer.
+    pub async fn reclaim_vault(&mut self, with_pool: bool, token_mint: Option<Pubkey>) -> Result<(), BanksClientError> {
+        let pool = self.pool();
+        let ix = instruction(
+            banger_program::accounts::ReclaimVault {
+                caller: self.ctx.payer.pubkey(),
+                creator_profile: creator_profile(),
+                creator_vault: creator_vault(),
+                treasury: treasury(),
+                pool: with_pool.then_some(pool),
+                config: config(),
+                mint: token_mint,
+                vault_ata: token_mint.map(|m| get_associated_token_address(&creator_vault(), &m)),
+                treasury_ata: token_mint.map(|m| get_associated_token_address(&treasury(), &m)),
+                quote_reserve: None,
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::ReclaimVault {
+                creator_id: CREATOR_ID.to_owned()
+            }
+        );
+        send(&mut self.ctx, &[ix], &[]).await
+    }
+
+    /// A plain SPL mint with `amount` tokens minted to `owner`'s ATA.
+    pub async fn spl_mint(&mut self, owner: &Pubkey, amount: u64) -> Pubkey {
+        let mint = create_mint(&mut self.ctx, 0).await;
+        self.mint_to(&mint, owner, amount).await;
+        mint
+    }
+
+    /// Mints `amount` of a mint created by the payer to `owner`'s ATA, creating it if needed.
+    pub async fn mint_to(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        let payer = self.ctx.payer.pubkey();
+        let ixs = [
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &payer,
+                owner,
+                mint,
+                &spl_token::ID
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                mint,
+                &get_associated_token_address(owner, mint),
+                &payer,
+                &[],
+                amount
+            ).unwrap()
+        ];
+        send(&mut self.ctx, &ixs, &[]).await.unwrap();
+    }
+
+    pub async fn migrate_pool(&mut self, mint: &Pubkey, creator_id: &str) -> Result<(), BanksClientError> {
+        let pool = pool(mint);
+        let ix = instruction(
+            banger_program::accounts::MigratePool {
+                admin: self.admin.pubkey(),
+                config: config(),
+                treasury: treasury(),
+                pool,
+                curve: curve(),
+                mint: *mint,
+                observations: observations(&pool),
+                creator_profile: creator_profile_of(creator_id),
+                system_program: system_program::ID
+            },
+            banger_program::instruction::MigratePool {
+                creator_id: creator_id.to_owned()
+            }
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    /// Links `wallet` to `CREATOR_ID`, signed by the admin as creator verifier.
+    pub async fn link_creator(&mut self, wallet: &Pubkey) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::LinkCreator {
+                creator_verifier: self.admin.pubkey(),
+                config: config(),
+                creator_profile: creator_profile(),
+                system_program: system_program::ID
+            },
+            banger_program::instruction::LinkCreator {
+                creator_id: CREATOR_ID.to_owned(),
+                wallet: *wallet
+            }
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    pub async fn claim_pool(&mut self, creator: &Keypair) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::ClaimPool {
+                creator: creator.pubkey(),
+                creator_profile: creator_profile(),
+                pool: self.pool()
+            },
+            banger_program::instruction::ClaimPool {}
+        );
+        send(&mut self.ctx, &[ix], &[creator]).await
+    }
+
+    pub async fn claim(&mut self, creator: &Keypair) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::Claim {
+                creator: creator.pubkey(),
+                creator_profile: creator_profile(),
+                creator_vault: creator_vault(),
+                config: config(),
+                system_program: system_program::ID
+            },
+            banger_program::instruction::Claim {
+                creator_id: CREATOR_ID.to_owned()
+            }
+        );
+        send(&mut self.ctx, &[ix], &[creator]).await
+    }
+
+    /// Withdraws to the admin, with no treasury split configured.
+    pub async fn withdraw_treasury(&mut self, amount: u64) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::WithdrawTreasury {
+                admin: self.admin.pubkey(),
+                config: config(),
+                treasury: treasury(),
+                system_program: system_program::ID
+            },
+            banger_program::instruction::WithdrawTreasury { amount }
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    pub async fn set_treasury_split(&mut self, split: Vec<TreasuryShare>) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            banger_program::accounts::SetTreasurySplit {
+                admin: self.admin.pubkey(),
+                config: config()
+            },
+            banger_program::instruction::SetTreasurySplit { split }
+        );
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    /// Withdraws `mint` from the treasury's ATA, to the ATAs of `recipients` when a split is configured.
+    pub async fn withdraw_treasury_tokens(&mut self, mint: &Pubkey, amount: u64, recipients: &[Pubkey]) -> Result<(), BanksClientError> {
+        let admin = self.admin.pubkey();
+        let mut ix = instruction(
+            banger_program::accounts::WithdrawTreasuryTokens {
+                admin,
+                config: config(),
+                treasury: treasury(),
+                mint: *mint,
+                treasury_ata: get_associated_token_address(&treasury(), mint),
+                admin_ata: recipients.is_empty().then(|| get_associated_token_address(&admin, mint)),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID
+            },
+            banger_program::instruction::WithdrawTreasuryTokens { amount }
+        );
+        ix.accounts.extend(recipients.iter().map(|r| AccountMeta::new(get_associated_token_address(r, mint), false)));
+        send(&mut self.ctx, &[ix], &[&self.admin]).await
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    pub async fn supply(&mut self) -> u64 {
+        self.account::<Mint>(&self.mint.clone()).await.supply
+    }
+
+    pub async fn supply_of(&mut self, mint: &Pubkey) -> u64 {
+        self.account::<Mint>(mint).await.supply
+    }
+
+    pub async fn token_balance_of(&mut self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let ata = get_associated_token_address(owner, mint);
+        self.account::<TokenAccount>(&ata).await.amount
+    }
+
+    /// Balance of the token account at `address`, for those that aren't ATAs.
+    pub async fn account_amount(&mut self, address: &Pubkey) -> u64 {
+        self.account::<TokenAccount>(address).await.amount
+    }
+
+    pub async fn token_balance(&mut self, owner: &Pubkey) -> u64 {
+        let ata = get_associated_token_address(owner, &self.mint);
+        self.account::<TokenAccount>(&ata).await.amount
+    }
+
+    async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+}
+
+/// A fresh SPL mint with the payer as its mint authority.
+pub async fn create_mint(ctx: &mut ProgramTestContext, decimals: u8) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = ctx.payer.pubkey();
+    let ixs = [
+        anchor_lang::solana_program::system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            Rent::default().minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &payer, None, decimals).unwrap()
+    ];
+    send(ctx, &ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+pub async fn send(
+    ctx: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair]
+) -> Result<(), BanksClientError> {
+    let mut blockhash = ctx.banks_client.get_latest_blockhash().await?;
+
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+
+    let mut tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&ctx.payer.pubkey()),
+        &all_signers,
+        blockhash
+    );
+
+    // The same transaction sent again under the same blockhash, typically a retry after
+    // an expected failure, would only return the first result
+    while ctx.banks_client.get_transaction_status(tx.signatures[0]).await?.is_some() {
+        blockhash = ctx.banks_client.get_new_latest_blockhash(&blockhash).await?;
+        tx.sign(&all_signers, blockhash);
+    }
+
+    ctx.banks_client.process_transaction(tx).await
+}
@@ -0,0 +1,131 @@
+//! Stand-in for the parts of Token Metadata the program calls: `CreateV1`,
+//! `MintV1` and `BurnV1` on fungible assets. It only does the token side of
+//! each instruction, metadata accounts hold nothing but their mint.
+//!
+//! Only used with `--features metadata-stub`, the dumped binary runs otherwise.
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar
+};
+
+const BURN: u8 = 41;
+const CREATE: u8 = 42;
+const MINT: u8 = 43;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8]
+) -> ProgramResult {
+    match data {
+        [CREATE, 0, ..] => create(program_id, accounts),
+        [MINT, 0, args @ ..] => mint(accounts, amount(args)?),
+        [BURN, 0, args @ ..] => burn(accounts, amount(args)?),
+        _ => Err(ProgramError::InvalidInstructionData)
+    }
+}
+
+fn amount(args: &[u8]) -> Result<u64, ProgramError> {
+    let bytes = args.get(..8).ok_or(ProgramError::InvalidInstructionData)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+// metadata, master_edition, mint, authority, payer, update_authority, ..
+fn create(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [metadata, _, mint, _, payer, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let seeds: &[&[u8]] = &[b"metadata", program_id.as_ref(), mint.key.as_ref()];
+    let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+    if address != *metadata.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let space = 32;
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            metadata.key,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            program_id
+        ),
+        &[payer.clone(), metadata.clone()],
+        &[&[b"metadata", program_id.as_ref(), mint.key.as_ref(), &[bump]]]
+    )?;
+
+    metadata.try_borrow_mut_data()?.copy_from_slice(mint.key.as_ref());
+
+    Ok(())
+}
+
+// token, token_owner, metadata, master_edition, token_record, mint, authority,
+// delegate_record, payer, system_program, sysvar_instructions, spl_token_program,
+// spl_ata_program, ..
+fn mint(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let [token, token_owner, _, _, _, mint, authority, _, payer, system_program, _, spl_token_program, spl_ata_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Token Metadata creates missing ATAs
+    if token.data_is_empty() {
+        invoke(
+            &spl_associated_token_account::instruction::create_associated_token_account(
+                payer.key,
+                token_owner.key,
+                mint.key,
+                spl_token_program.key
+            ),
+            &[
+                payer.clone(),
+                token.clone(),
+                token_owner.clone(),
+                mint.clone(),
+                system_program.clone(),
+                spl_token_program.clone(),
+                spl_ata_program.clone()
+            ]
+        )?;
+    }
+
+    invoke(
+        &spl_token::instruction::mint_to(
+            spl_token_program.key,
+            mint.key,
+            token.key,
+            authority.key,
+            &[],
+            amount
+        )?,
+        &[mint.clone(), token.clone(), authority.clone(), spl_token_program.clone()]
+    )
+}
+
+// authority, collection_metadata, metadata, edition, mint, token, master_edition,
+// master_edition_mint, master_edition_token, edition_marker, token_record,
+// system_program, sysvar_instructions, spl_token_program
+fn burn(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let [authority, _, _, _, mint, token, _, _, _, _, _, _, _, spl_token_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    invoke(
+        &spl_token::instruction::burn(
+            spl_token_program.key,
+            token.key,
+            mint.key,
+            authority.key,
+            &[],
+            amount
+        )?,
+        &[token.clone(), mint.clone(), authority.clone(), spl_token_program.clone()]
+    )
+}
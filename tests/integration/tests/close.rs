@@ -0,0 +1,111 @@
+use anchor_spl::associated_token::get_associated_token_address;
+use banger_program::OrderSide;
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+const AMOUNT: u64 = 20_000_000;
+
+// `MIN_STAKE_DURATION`
+const LOCK: i64 = 24 * 60 * 60;
+
+#[tokio::test]
+async fn creator_closes_pool_and_rent_goes_to_payer() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&trader, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+    assert!(env.close_pool(&creator).await.is_err());
+    env.sell(&trader, AMOUNT, 0).await.unwrap();
+
+    assert!(env.close_pool(&trader).await.is_err());
+
+    let pool = env.pool();
+    let rent = env.lamports(&pool).await + env.lamports(&observations(&pool)).await;
+    let admin = env.admin.pubkey();
+    let before = env.lamports(&admin).await;
+    let creator_before = env.lamports(&creator.pubkey()).await;
+
+    env.close_pool(&creator).await.unwrap();
+    assert_eq!(env.lamports(&pool).await, 0);
+    assert_eq!(env.lamports(&admin).await, before + rent);
+    assert_eq!(env.lamports(&creator.pubkey()).await, creator_before);
+}
+
+#[tokio::test]
+async fn close_waits_for_stakers_to_claim() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    env.set_staking_share(&creator, 10000).await.unwrap();
+
+    let staker = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&staker, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+    env.stake(&staker, AMOUNT).await.unwrap();
+
+    // Fees on this round trip go to the staker
+    let trader = env.wallet(2 * LAMPORTS_PER_SOL).await;
+    env.buy(&trader, LAMPORTS_PER_SOL, AMOUNT).await.unwrap();
+    env.sell(&trader, AMOUNT, 0).await.unwrap();
+
+    env.warp(LOCK).await;
+    env.unstake(&staker, AMOUNT).await.unwrap();
+    env.sell(&staker, AMOUNT, 0).await.unwrap();
+    assert_eq!(env.supply().await, 0);
+
+    assert!(env.close_pool(&creator).await.is_err());
+
+    let pool = env.pool();
+    env.claim_rewards(&staker).await.unwrap();
+    assert_eq!(env.lamports(&stake(&pool, &staker.pubkey())).await, 0);
+
+    // The stake vault's rent goes back with the pool's
+    let vault = stake_vault(&pool);
+    let rent = env.lamports(&pool).await + env.lamports(&observations(&pool)).await + env.lamports(&vault).await;
+    let admin = env.admin.pubkey();
+    let before = env.lamports(&admin).await;
+
+    env.close_pool(&creator).await.unwrap();
+    assert_eq!(env.lamports(&vault).await, 0);
+    assert_eq!(env.lamports(&admin).await, before + rent);
+}
+
+#[tokio::test]
+async fn quote_pool_close_refunds_the_payer() {
+    let mut env = Env::start_quoted().await;
+    let quote = env.quote_mint.unwrap();
+    let pool = env.pool();
+    let reserve = quote_reserve(&pool);
+
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    env.mint_to(&quote, &trader.pubkey(), LAMPORTS_PER_SOL).await;
+    env.buy(&trader, LAMPORTS_PER_SOL, AMOUNT).await.unwrap();
+    env.sell(&trader, AMOUNT, 0).await.unwrap();
+
+    // Rounding dust in the reserve goes to the payer, like the rent
+    let dust = env.account_amount(&reserve).await;
+    let rent = env.lamports(&pool).await + env.lamports(&observations(&pool)).await + env.lamports(&reserve).await;
+    let admin = env.admin.insecure_clone();
+    let before = env.lamports(&admin.pubkey()).await;
+
+    env.close_pool(&admin).await.unwrap();
+    assert_eq!(env.lamports(&reserve).await, 0);
+    assert_eq!(env.token_balance_of(&admin.pubkey(), &quote).await, dust);
+    let ata_rent = env.lamports(&get_associated_token_address(&admin.pubkey(), &quote)).await;
+    assert_eq!(env.lamports(&admin.pubkey()).await + ata_rent, before + rent);
+}
+
+#[tokio::test]
+async fn buy_orders_cancel_after_close() {
+    let mut env = Env::start().await;
+    let owner = env.wallet(LAMPORTS_PER_SOL).await;
+    env.place_order(&owner, 0, OrderSide::Buy, AMOUNT, 1, LAMPORTS_PER_SOL / 2).await.unwrap();
+
+    let admin = env.admin.insecure_clone();
+    env.close_pool(&admin).await.unwrap();
+
+    let before = env.lamports(&owner.pubkey()).await;
+    env.cancel_order(&owner, 0, false).await.unwrap();
+    assert!(env.lamports(&owner.pubkey()).await > before + LAMPORTS_PER_SOL / 2);
+}
@@ -0,0 +1,22 @@
+use banger_program_tests::*;
+use solana_sdk::{account::Account, native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+#[tokio::test]
+async fn init_config_requires_upgrade_authority() {
+    let authority = Keypair::new();
+
+    let mut program_test = program_test();
+    program_test.add_account(authority.pubkey(), Account {
+        lamports: LAMPORTS_PER_SOL,
+        ..Account::default()
+    });
+    program_test.add_account(program_data(), program_data_account(&authority.pubkey()));
+    let mut ctx = program_test.start_with_context().await;
+
+    // Anyone else, like the fee payer, is rejected
+    let payer = ctx.payer.pubkey();
+    assert!(send(&mut ctx, &[init_config(&payer)], &[]).await.is_err());
+
+    send(&mut ctx, &[init_config(&authority.pubkey())], &[&authority]).await.unwrap();
+    assert!(ctx.banks_client.get_account(config()).await.unwrap().is_some());
+}
@@ -0,0 +1,210 @@
+use anchor_spl::associated_token::get_associated_token_address;
+use banger_program::LaunchSettings;
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+const VESTING: i64 = 30 * 24 * 60 * 60;
+
+#[tokio::test]
+async fn creator_replaces_unclaimed_pool() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    let squatted = env.pool();
+
+    let holder = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&holder, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+
+    // The existing pool has to be named to be replaced
+    let mint = Keypair::new();
+    let ix = launch_creator_pool(&creator.pubkey(), &mint.pubkey(), None);
+    assert!(send(&mut env.ctx, &[ix], &[&creator, &mint]).await.is_err());
+
+    let ix = launch_creator_pool(&creator.pubkey(), &mint.pubkey(), Some(squatted));
+    send(&mut env.ctx, &[ix], &[&creator, &mint]).await.unwrap();
+
+    // The new pool starts verified, so it can't be replaced in turn
+    let again = Keypair::new();
+    let ix = launch_creator_pool(&creator.pubkey(), &again.pubkey(), Some(pool(&mint.pubkey())));
+    assert!(send(&mut env.ctx, &[ix], &[&creator, &again]).await.is_err());
+
+    // The old pool stops selling tokens, but its holders can still get out
+    assert!(env.buy(&creator, LAMPORTS_PER_SOL / 2, 20_000_000).await.is_err());
+    env.sell(&holder, 20_000_000, 0).await.unwrap();
+
+    // Nor can it be claimed back as a second verified pool
+    assert!(env.claim_pool(&creator).await.is_err());
+}
+
+#[tokio::test]
+async fn creator_allocation_stays_out_of_holdings() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+
+    let mint = Keypair::new();
+    let ixs = [
+        launch_creator_pool(&creator.pubkey(), &mint.pubkey(), Some(env.pool())),
+        reserve_creator_allocation(&creator.pubkey(), &mint.pubkey(), 20_000_000, LAMPORTS_PER_SOL / 2, VESTING, None)
+    ];
+    send(&mut env.ctx, &ixs, &[&creator, &mint]).await.unwrap();
+
+    // Paid for, but the tokens are in escrow and not the creator's wallet
+    let pool = pool(&mint.pubkey());
+    let position = env.position_state(&pool, &creator.pubkey()).await;
+    assert_eq!(position.balance, 0);
+    assert!(position.spent > 0);
+
+    let stats = env.pool_state(&pool).await.stats;
+    assert_eq!(stats.holders, 0);
+    assert_eq!(stats.trade_count, 1);
+}
+
+#[tokio::test]
+async fn liquid_creator_allocation_skips_launch_limits() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    let trading_start = env.now().await + 3600;
+    let launch = LaunchSettings { trading_start, max_per_tx: 1, ..Default::default() };
+    env.set_launch_settings(&creator, launch).await.unwrap();
+
+    let ix = reserve_creator_allocation(&creator.pubkey(), &env.mint, 20_000_000, LAMPORTS_PER_SOL / 2, 0, None);
+    send(&mut env.ctx, &[ix], &[&creator]).await.unwrap();
+
+    // Straight to the creator's wallet and holdings, while everyone else waits
+    assert_eq!(env.token_balance(&creator.pubkey()).await, 20_000_000);
+    assert_eq!(env.position_state(&env.pool(), &creator.pubkey()).await.balance, 20_000_000);
+    assert_eq!(env.pool_state(&env.pool()).await.stats.holders, 1);
+
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    assert!(env.buy(&buyer, LAMPORTS_PER_SOL / 2, 1).await.is_err());
+}
+
+#[tokio::test]
+async fn creator_allocation_in_quote_pool() {
+    let mut env = Env::start_quoted().await;
+    let quote = env.quote_mint.unwrap();
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    env.mint_to(&quote, &creator.pubkey(), LAMPORTS_PER_SOL).await;
+
+    // Nothing in SOL, the escrow is paid for in the quote token
+    let ix = reserve_creator_allocation(&creator.pubkey(), &env.mint, 20_000_000, LAMPORTS_PER_SOL / 2, VESTING, Some(quote));
+    send(&mut env.ctx, &[ix], &[&creator]).await.unwrap();
+
+    let vesting = vesting(&env.mint, &creator.pubkey(), &creator.pubkey());
+    assert_eq!(env.account_amount(&vesting_escrow(&vesting)).await, 20_000_000);
+    assert!(env.account_amount(&quote_reserve(&env.pool())).await > 0);
+    assert!(env.token_balance_of(&creator.pubkey(), &quote).await < LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn creator_allocation_needs_an_untraded_pool() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+
+    // Supply is back to 0 after the sell, but the pool is past its launch
+    let holder = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&holder, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+    env.sell(&holder, 20_000_000, 0).await.unwrap();
+    assert_eq!(env.supply().await, 0);
+
+    let ix = reserve_creator_allocation(&creator.pubkey(), &env.mint, 20_000_000, LAMPORTS_PER_SOL / 2, VESTING, None);
+    assert!(send(&mut env.ctx, &[ix], &[&creator]).await.is_err());
+}
+
+#[tokio::test]
+async fn creator_allocation_leaves_launch_window_closed() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    let launch = LaunchSettings { launch_slots: 50, max_wallet_launch: 10_000_000, ..Default::default() };
+    env.set_launch_settings(&creator, launch).await.unwrap();
+
+    let ix = reserve_creator_allocation(&creator.pubkey(), &env.mint, 10_000_000, LAMPORTS_PER_SOL / 2, VESTING, None);
+    send(&mut env.ctx, &[ix], &[&creator]).await.unwrap();
+
+    // The window opens with the first buy, however long after the allocation
+    env.warp(0).await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, 10_000_000).await.unwrap();
+    assert!(env.buy(&buyer, LAMPORTS_PER_SOL / 2, 1).await.is_err());
+}
+
+#[tokio::test]
+async fn claimed_pool_cannot_be_replaced() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    env.claim_pool(&creator).await.unwrap();
+
+    let mint = Keypair::new();
+    let ix = launch_creator_pool(&creator.pubkey(), &mint.pubkey(), Some(env.pool()));
+    assert!(send(&mut env.ctx, &[ix], &[&creator, &mint]).await.is_err());
+}
+
+#[tokio::test]
+async fn only_linked_wallet_replaces_pool() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+
+    let other = env.wallet(LAMPORTS_PER_SOL).await;
+    let mint = Keypair::new();
+    let ix = launch_creator_pool(&other.pubkey(), &mint.pubkey(), Some(env.pool()));
+    assert!(send(&mut env.ctx, &[ix], &[&other, &mint]).await.is_err());
+}
+
+#[tokio::test]
+async fn only_linked_creator_changes_launch_settings() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+
+    let admin = env.admin.insecure_clone();
+    assert!(env.set_launch_settings(&admin, LaunchSettings::default()).await.is_err());
+
+    let launch = LaunchSettings { max_per_tx: 20_000_000, ..Default::default() };
+    env.set_launch_settings(&creator, launch).await.unwrap();
+
+    // Too late once trading started, even after the supply is sold back
+    env.buy(&creator, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+    env.sell(&creator, 20_000_000, 0).await.unwrap();
+    assert!(env.set_launch_settings(&creator, LaunchSettings::default()).await.is_err());
+}
+
+#[tokio::test]
+async fn only_linked_creator_sets_holder_tiers() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+
+    let admin = env.admin.insecure_clone();
+    assert!(env.set_holder_tiers(&admin, vec![1, 2]).await.is_err());
+
+    env.set_holder_tiers(&creator, vec![1, 2]).await.unwrap();
+}
+
+#[tokio::test]
+async fn closed_ata_downgrades_holder_badge() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    env.set_holder_tiers(&creator, vec![1, 2]).await.unwrap();
+
+    let holder = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&holder, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+    env.verify_holding(&holder.pubkey()).await.unwrap();
+    assert_eq!(env.holder_badge(&holder.pubkey()).await.tier, 2);
+
+    // Anyone can still refresh the badge once the holder sold out and closed the ATA
+    env.sell(&holder, 20_000_000, 0).await.unwrap();
+    let ata = get_associated_token_address(&holder.pubkey(), &env.mint);
+    let ix = spl_token::instruction::close_account(&spl_token::ID, &ata, &holder.pubkey(), &holder.pubkey(), &[]).unwrap();
+    send(&mut env.ctx, &[ix], &[&holder]).await.unwrap();
+
+    env.verify_holding(&holder.pubkey()).await.unwrap();
+    assert_eq!(env.holder_badge(&holder.pubkey()).await.tier, 0);
+}
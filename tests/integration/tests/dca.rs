@@ -0,0 +1,55 @@
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+const PER_INTERVAL: u64 = LAMPORTS_PER_SOL / 10;
+const INTERVAL: i64 = 60 * 60;
+
+#[tokio::test]
+async fn dca_buys_once_per_interval_from_the_deposit() {
+    let mut env = Env::start().await;
+    let owner = env.wallet(LAMPORTS_PER_SOL).await;
+    let cranker = env.wallet(LAMPORTS_PER_SOL).await;
+    let pool = env.pool();
+    let plan = dca_plan(&pool, &owner.pubkey());
+
+    env.open_dca(&owner, PER_INTERVAL, INTERVAL, u64::MAX, 3 * PER_INTERVAL).await.unwrap();
+
+    let plan_before = env.lamports(&plan).await;
+    let paid_in = env.lamports(&pool).await + env.lamports(&treasury()).await + env.lamports(&creator_vault()).await;
+
+    env.execute_dca(&cranker, &owner.pubkey()).await.unwrap();
+
+    let bought = env.token_balance(&owner.pubkey()).await;
+    assert!(bought > 0);
+    assert_eq!(env.supply().await, bought);
+    // Fees included, the buy stays within the interval's budget
+    let spent = plan_before - env.lamports(&plan).await;
+    assert!(spent > 0 && spent <= PER_INTERVAL);
+    let paid_out = env.lamports(&pool).await + env.lamports(&treasury()).await + env.lamports(&creator_vault()).await;
+    assert_eq!(paid_out - paid_in, spent);
+
+    assert!(env.execute_dca(&cranker, &owner.pubkey()).await.is_err());
+    env.warp(INTERVAL).await;
+    env.execute_dca(&cranker, &owner.pubkey()).await.unwrap();
+    assert!(env.token_balance(&owner.pubkey()).await > bought);
+
+    // Closing returns what's left of the deposit with the rent
+    let left = env.lamports(&plan).await;
+    let owner_before = env.lamports(&owner.pubkey()).await;
+    env.close_dca(&owner).await.unwrap();
+    assert_eq!(env.lamports(&plan).await, 0);
+    assert_eq!(env.lamports(&owner.pubkey()).await - owner_before, left);
+}
+
+#[tokio::test]
+async fn dca_waits_while_the_price_is_over_its_cap() {
+    let mut env = Env::start().await;
+    let whale = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&whale, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+
+    let owner = env.wallet(LAMPORTS_PER_SOL).await;
+    let cranker = env.wallet(LAMPORTS_PER_SOL).await;
+    env.open_dca(&owner, PER_INTERVAL, INTERVAL, 1, 3 * PER_INTERVAL).await.unwrap();
+
+    assert!(env.execute_dca(&cranker, &owner.pubkey()).await.is_err());
+}
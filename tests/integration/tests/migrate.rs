@@ -0,0 +1,69 @@
+use anchor_lang::prelude::Rent;
+use banger_program::LaunchSettings;
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+const LEGACY_ID: &str = "legacy";
+
+#[tokio::test]
+async fn migrated_pool_takes_its_creator_profile() {
+    let mut env = Env::start().await;
+    let admin = env.admin.pubkey();
+
+    let legacy_mint = create_mint(&mut env.ctx, 6).await;
+    let legacy = pool(&legacy_mint);
+    env.ctx.set_account(&legacy, &legacy_pool_account(&admin, &legacy_mint, LEGACY_ID).into());
+
+    // The id has to be the pool's own
+    assert!(env.migrate_pool(&legacy_mint, CREATOR_ID).await.is_err());
+    env.migrate_pool(&legacy_mint, LEGACY_ID).await.unwrap();
+
+    // So no second pool can be launched for it
+    let mint = Keypair::new();
+    let ix = init_pool(&admin, &mint.pubkey(), LEGACY_ID, LaunchSettings::default());
+    let admin_signer = env.admin.insecure_clone();
+    assert!(send(&mut env.ctx, &[ix], &[&admin_signer, &mint]).await.is_err());
+}
+
+#[tokio::test]
+async fn migrated_pool_pays_fees_to_treasury() {
+    let mut env = Env::start().await;
+    let admin = env.admin.pubkey();
+    let pool = env.pool();
+    let mint = env.mint;
+    env.ctx.set_account(&pool, &legacy_pool_account(&admin, &mint, CREATOR_ID).into());
+    env.migrate_pool(&mint, CREATOR_ID).await.unwrap();
+
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    let before = env.lamports(&treasury()).await;
+    env.buy(&trader, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+    assert!(env.lamports(&treasury()).await > before);
+}
+
+#[tokio::test]
+async fn migrated_pool_with_supply_sells_out() {
+    let mut env = Env::start().await;
+    let admin = env.admin.pubkey();
+    let pool = env.pool();
+    let mint = env.mint;
+
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&trader, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+    let account = env.ctx.banks_client.get_account(pool).await.unwrap().unwrap();
+    let reserve = account.lamports - Rent::default().minimum_balance(account.data.len());
+
+    // Legacy pricing left the reserve short of what the curve pays out
+    let mut legacy = legacy_pool_account(&admin, &mint, CREATOR_ID);
+    legacy.lamports += reserve / 2;
+    env.ctx.set_account(&pool, &legacy.clone().into());
+    assert!(env.migrate_pool(&mint, CREATOR_ID).await.is_err());
+
+    // Once topped up, every holder can sell out
+    legacy.lamports += reserve - reserve / 2;
+    env.ctx.set_account(&pool, &legacy.into());
+    env.migrate_pool(&mint, CREATOR_ID).await.unwrap();
+
+    let balance = env.token_balance(&trader.pubkey()).await;
+    env.sell(&trader, balance, 0).await.unwrap();
+    assert_eq!(env.supply_of(&mint).await, 0);
+}
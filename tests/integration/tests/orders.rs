@@ -0,0 +1,97 @@
+use banger_program::OrderSide;
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+const AMOUNT: u64 = 20_000_000;
+
+// Same trades as in pool.rs
+const BUY_SUBTOTAL: u64 = 83_333_334;
+const BUY_FEE: u64 = 4_166_666;
+const SELL_AMOUNT: u64 = 10_000_000;
+const SELL_TOTAL: u64 = 72_916_666;
+const SELL_FEE: u64 = 3_645_833;
+
+#[tokio::test]
+async fn cancel_sell_order_returns_escrowed_tokens() {
+    let mut env = Env::start().await;
+    let owner = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&owner, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+
+    env.place_order(&owner, 0, OrderSide::Sell, AMOUNT, u64::MAX, 0).await.unwrap();
+    assert_eq!(env.token_balance(&owner.pubkey()).await, 0);
+
+    // Leaving the escrow out would close the order and strand the tokens
+    assert!(env.cancel_order(&owner, 0, false).await.is_err());
+
+    env.cancel_order(&owner, 0, true).await.unwrap();
+    assert_eq!(env.token_balance(&owner.pubkey()).await, AMOUNT);
+}
+
+#[tokio::test]
+async fn cancel_buy_order_needs_no_escrow() {
+    let mut env = Env::start().await;
+    let owner = env.wallet(LAMPORTS_PER_SOL).await;
+
+    env.place_order(&owner, 0, OrderSide::Buy, AMOUNT, 1, LAMPORTS_PER_SOL / 2).await.unwrap();
+    let order = order(&env.pool(), &owner.pubkey(), 0);
+    assert!(env.lamports(&order).await > LAMPORTS_PER_SOL / 2);
+
+    env.cancel_order(&owner, 0, false).await.unwrap();
+    assert_eq!(env.lamports(&order).await, 0);
+}
+
+#[tokio::test]
+async fn keeper_executes_buy_order() {
+    let mut env = Env::start().await;
+    let owner = env.wallet(LAMPORTS_PER_SOL).await;
+    let keeper = env.wallet(LAMPORTS_PER_SOL).await;
+
+    env.place_order(&owner, 0, OrderSide::Buy, AMOUNT, u64::MAX, LAMPORTS_PER_SOL / 2).await.unwrap();
+    let order = order(&env.pool(), &owner.pubkey(), 0);
+    let escrowed = env.lamports(&order).await;
+    let owner_before = env.lamports(&owner.pubkey()).await;
+    let treasury_before = env.lamports(&treasury()).await;
+    let vault_before = env.lamports(&creator_vault()).await;
+
+    env.execute_order(&keeper, &owner.pubkey(), 0, OrderSide::Buy).await.unwrap();
+
+    assert_eq!(env.supply().await, AMOUNT);
+    assert_eq!(env.token_balance(&owner.pubkey()).await, AMOUNT);
+    assert_eq!(env.lamports(&order).await, 0);
+    assert_eq!(env.lamports(&treasury()).await - treasury_before, BUY_FEE);
+    assert_eq!(env.lamports(&creator_vault()).await - vault_before, BUY_FEE);
+    // What the fill didn't spend comes back with the order's rent
+    let total = BUY_SUBTOTAL + 2 * BUY_FEE;
+    assert_eq!(env.lamports(&owner.pubkey()).await - owner_before, escrowed - total);
+}
+
+#[tokio::test]
+async fn keeper_executes_sell_order() {
+    let mut env = Env::start().await;
+    let owner = env.wallet(LAMPORTS_PER_SOL).await;
+    let keeper = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&owner, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+
+    // Not before the price reaches the trigger
+    env.place_order(&owner, 0, OrderSide::Sell, SELL_AMOUNT, u64::MAX, 0).await.unwrap();
+    assert!(env.execute_order(&keeper, &owner.pubkey(), 0, OrderSide::Sell).await.is_err());
+    env.cancel_order(&owner, 0, true).await.unwrap();
+
+    env.place_order(&owner, 1, OrderSide::Sell, SELL_AMOUNT, 0, 0).await.unwrap();
+    let order = order(&env.pool(), &owner.pubkey(), 1);
+    let escrow = order_escrow(&order);
+    let rent = env.lamports(&order).await + env.lamports(&escrow).await;
+    let owner_before = env.lamports(&owner.pubkey()).await;
+    let treasury_before = env.lamports(&treasury()).await;
+    let vault_before = env.lamports(&creator_vault()).await;
+
+    env.execute_order(&keeper, &owner.pubkey(), 1, OrderSide::Sell).await.unwrap();
+
+    assert_eq!(env.supply().await, AMOUNT - SELL_AMOUNT);
+    assert_eq!(env.token_balance(&owner.pubkey()).await, AMOUNT - SELL_AMOUNT);
+    assert_eq!(env.lamports(&escrow).await, 0);
+    assert_eq!(env.lamports(&treasury()).await - treasury_before, SELL_FEE);
+    assert_eq!(env.lamports(&creator_vault()).await - vault_before, SELL_FEE);
+    let proceeds = SELL_TOTAL - 2 * SELL_FEE;
+    assert_eq!(env.lamports(&owner.pubkey()).await - owner_before, proceeds + rent);
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::{Pubkey, Rent};
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+// 20 whole tokens at 6 decimals on a pow 2 / frac 32000 curve
+const BUY_AMOUNT: u64 = 20_000_000;
+const BUY_SUBTOTAL: u64 = 83_333_334;
+const BUY_FEE: u64 = 4_166_666;
+
+// Selling half of it back
+const SELL_AMOUNT: u64 = 10_000_000;
+const SELL_TOTAL: u64 = 72_916_666;
+const SELL_FEE: u64 = 3_645_833;
+
+#[tokio::test]
+async fn init_pool_creates_empty_pool() {
+    let mut env = Env::start().await;
+
+    assert_eq!(env.supply().await, 0);
+    let metadata = metadata(&env.mint);
+    assert!(env.ctx.banks_client.get_account(metadata).await.unwrap().is_some());
+    assert!(env.ctx.banks_client.get_account(env.pool()).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn buy_mints_tokens_and_splits_fees() {
+    let mut env = Env::start().await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    let pool = env.pool();
+
+    let pool_before = env.lamports(&pool).await;
+    let treasury_before = env.lamports(&treasury()).await;
+    let vault_before = env.lamports(&creator_vault()).await;
+
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+
+    assert_eq!(env.supply().await, BUY_AMOUNT);
+    assert_eq!(env.token_balance(&buyer.pubkey()).await, BUY_AMOUNT);
+    assert_eq!(env.lamports(&pool).await - pool_before, BUY_SUBTOTAL);
+    assert_eq!(env.lamports(&treasury()).await - treasury_before, BUY_FEE);
+    assert_eq!(env.lamports(&creator_vault()).await - vault_before, BUY_FEE);
+}
+
+#[tokio::test]
+async fn buy_respects_slippage() {
+    let mut env = Env::start().await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+
+    let total = BUY_SUBTOTAL + 2 * BUY_FEE;
+    assert!(env.buy(&buyer, total - 1, BUY_AMOUNT).await.is_err());
+    env.buy(&buyer, total, BUY_AMOUNT).await.unwrap();
+
+    assert_eq!(env.supply().await, BUY_AMOUNT);
+}
+
+#[tokio::test]
+async fn sell_burns_tokens_and_pays_out() {
+    let mut env = Env::start().await;
+    let seller = env.wallet(LAMPORTS_PER_SOL).await;
+    let pool = env.pool();
+    env.buy(&seller, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+
+    let pool_before = env.lamports(&pool).await;
+    let treasury_before = env.lamports(&treasury()).await;
+    let vault_before = env.lamports(&creator_vault()).await;
+    let seller_before = env.lamports(&seller.pubkey()).await;
+
+    let proceeds = SELL_TOTAL - 2 * SELL_FEE;
+    assert!(env.sell(&seller, SELL_AMOUNT, proceeds + 1).await.is_err());
+    env.sell(&seller, SELL_AMOUNT, proceeds).await.unwrap();
+
+    assert_eq!(env.supply().await, BUY_AMOUNT - SELL_AMOUNT);
+    assert_eq!(env.token_balance(&seller.pubkey()).await, BUY_AMOUNT - SELL_AMOUNT);
+    assert_eq!(pool_before - env.lamports(&pool).await, SELL_TOTAL);
+    assert_eq!(env.lamports(&treasury()).await - treasury_before, SELL_FEE);
+    assert_eq!(env.lamports(&creator_vault()).await - vault_before, SELL_FEE);
+    // The payer covers transaction fees, so the seller gets exactly the proceeds
+    assert_eq!(env.lamports(&seller.pubkey()).await - seller_before, proceeds);
+}
+
+#[tokio::test]
+async fn selling_everything_empties_reserve() {
+    let mut env = Env::start().await;
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    let pool = env.pool();
+    let rent_floor = env.lamports(&pool).await;
+
+    env.buy(&trader, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+    env.sell(&trader, BUY_AMOUNT, 0).await.unwrap();
+
+    assert_eq!(env.supply().await, 0);
+    // Rounding favours the pool, never the trader
+    assert!(env.lamports(&pool).await >= rent_floor);
+}
+
+#[tokio::test]
+async fn claim_pays_linked_creator() {
+    let mut env = Env::start().await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    assert!(env.claim(&creator).await.is_err());
+
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    let vault_before = env.lamports(&creator_vault()).await;
+    let creator_before = env.lamports(&creator.pubkey()).await;
+
+    env.claim(&creator).await.unwrap();
+
+    let claimed = env.lamports(&creator.pubkey()).await - creator_before;
+    assert!(claimed >= BUY_FEE);
+    assert_eq!(vault_before - env.lamports(&creator_vault()).await, claimed);
+}
+
+#[tokio::test]
+async fn claim_rejects_other_wallets() {
+    let mut env = Env::start().await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+
+    env.link_creator(&Pubkey::new_unique()).await.unwrap();
+    assert!(env.claim(&buyer).await.is_err());
+}
+
+#[tokio::test]
+async fn launch_limits_apply_from_first_buy() {
+    let mut env = Env::start_with(banger_program::LaunchSettings {
+        max_per_tx: BUY_AMOUNT,
+        ..Default::default()
+    }).await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+
+    assert!(env.buy(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT + 1).await.is_err());
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+}
+
+#[tokio::test]
+async fn wallet_cap_starts_with_trading() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    let trading_start = env.now().await + 60;
+    env.set_launch_settings(&creator, banger_program::LaunchSettings {
+        trading_start,
+        launch_slots: 50,
+        max_wallet_launch: BUY_AMOUNT,
+        ..Default::default()
+    }).await.unwrap();
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    assert!(env.buy(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.is_err());
+
+    // Trading opens more than `launch_slots` after creation, the cap still holds
+    env.warp(120).await;
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+    assert!(env.buy(&buyer, LAMPORTS_PER_SOL / 2, 1).await.is_err());
+
+    env.warp(0).await;
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, 1).await.unwrap();
+}
+
+#[tokio::test]
+async fn basket_buy_tops_up_prefunded_position() {
+    let mut env = Env::start().await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    let pool = env.pool();
+
+    // Anyone can send lamports to the position address before its first basket buy
+    let griefer = env.wallet(LAMPORTS_PER_SOL).await;
+    let transfer = anchor_lang::solana_program::system_instruction::transfer(
+        &griefer.pubkey(),
+        &position(&pool, &buyer.pubkey()),
+        Rent::default().minimum_balance(0)
+    );
+    send(&mut env.ctx, &[transfer], &[&griefer]).await.unwrap();
+
+    env.buy_basket(&buyer, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+    assert_eq!(env.token_balance(&buyer.pubkey()).await, BUY_AMOUNT);
+}
@@ -0,0 +1,44 @@
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+// The trades of pool.rs, the quote token has SOL's decimals so the amounts carry over
+const BUY_AMOUNT: u64 = 20_000_000;
+const BUY_SUBTOTAL: u64 = 83_333_334;
+const BUY_FEE: u64 = 4_166_666;
+const SELL_AMOUNT: u64 = 10_000_000;
+const SELL_TOTAL: u64 = 72_916_666;
+const SELL_FEE: u64 = 3_645_833;
+
+const FUNDS: u64 = LAMPORTS_PER_SOL;
+
+#[tokio::test]
+async fn quote_pool_trades_in_the_quote_token() {
+    let mut env = Env::start_quoted().await;
+    let quote = env.quote_mint.unwrap();
+    let pool = env.pool();
+    let reserve = quote_reserve(&pool);
+
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    env.mint_to(&quote, &trader.pubkey(), FUNDS).await;
+    let pool_lamports = env.lamports(&pool).await;
+
+    env.buy(&trader, FUNDS, BUY_AMOUNT).await.unwrap();
+
+    assert_eq!(env.token_balance(&trader.pubkey()).await, BUY_AMOUNT);
+    assert_eq!(env.token_balance_of(&trader.pubkey(), &quote).await, FUNDS - BUY_SUBTOTAL - 2 * BUY_FEE);
+    assert_eq!(env.account_amount(&reserve).await, BUY_SUBTOTAL);
+    assert_eq!(env.token_balance_of(&treasury(), &quote).await, BUY_FEE);
+    assert_eq!(env.token_balance_of(&creator_vault(), &quote).await, BUY_FEE);
+
+    let proceeds = SELL_TOTAL - 2 * SELL_FEE;
+    assert!(env.sell(&trader, SELL_AMOUNT, proceeds + 1).await.is_err());
+    env.sell(&trader, SELL_AMOUNT, proceeds).await.unwrap();
+
+    assert_eq!(env.supply().await, BUY_AMOUNT - SELL_AMOUNT);
+    assert_eq!(env.token_balance_of(&trader.pubkey(), &quote).await, FUNDS - BUY_SUBTOTAL - 2 * BUY_FEE + proceeds);
+    assert_eq!(env.account_amount(&reserve).await, BUY_SUBTOTAL - SELL_TOTAL);
+    assert_eq!(env.token_balance_of(&treasury(), &quote).await, BUY_FEE + SELL_FEE);
+    assert_eq!(env.token_balance_of(&creator_vault(), &quote).await, BUY_FEE + SELL_FEE);
+    // No SOL moves through the pool
+    assert_eq!(env.lamports(&pool).await, pool_lamports);
+}
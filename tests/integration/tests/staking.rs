@@ -0,0 +1,64 @@
+use banger_program::PAUSE_CLAIM;
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+const AMOUNT: u64 = 20_000_000;
+
+// `MIN_STAKE_DURATION`
+const LOCK: i64 = 24 * 60 * 60;
+
+#[tokio::test]
+async fn only_linked_creator_sets_staking_share() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+
+    // The admin launched the pool, but it isn't theirs
+    let admin = env.admin.insecure_clone();
+    assert!(env.set_staking_share(&admin, 10000).await.is_err());
+
+    env.set_staking_share(&creator, 5000).await.unwrap();
+}
+
+#[tokio::test]
+async fn stake_stays_locked_after_staking() {
+    let mut env = Env::start().await;
+    let staker = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&staker, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+
+    env.stake(&staker, AMOUNT / 2).await.unwrap();
+    assert!(env.unstake(&staker, AMOUNT / 2).await.is_err());
+
+    // Staking more restarts the lock
+    env.warp(LOCK - 60).await;
+    env.stake(&staker, AMOUNT / 2).await.unwrap();
+    env.warp(60).await;
+    assert!(env.unstake(&staker, AMOUNT).await.is_err());
+
+    env.warp(LOCK).await;
+    env.unstake(&staker, AMOUNT).await.unwrap();
+    assert_eq!(env.token_balance(&staker.pubkey()).await, AMOUNT);
+}
+
+#[tokio::test]
+async fn paused_claims_hold_rewards() {
+    let mut env = Env::start().await;
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    env.set_staking_share(&creator, 10000).await.unwrap();
+
+    let staker = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&staker, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+    env.stake(&staker, AMOUNT).await.unwrap();
+
+    let trader = env.wallet(2 * LAMPORTS_PER_SOL).await;
+    env.buy(&trader, LAMPORTS_PER_SOL, AMOUNT).await.unwrap();
+
+    env.pause(PAUSE_CLAIM, true).await.unwrap();
+    assert!(env.claim_rewards(&staker).await.is_err());
+    env.unpause(PAUSE_CLAIM, true).await.unwrap();
+
+    let before = env.lamports(&staker.pubkey()).await;
+    env.claim_rewards(&staker).await.unwrap();
+    assert!(env.lamports(&staker.pubkey()).await > before);
+}
@@ -0,0 +1,63 @@
+use anchor_spl::associated_token::get_associated_token_address;
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+// Same trades as in pool.rs: 20 whole tokens bought, half of them swapped
+const BUY_AMOUNT: u64 = 20_000_000;
+const SWAP_AMOUNT: u64 = 10_000_000;
+const SELL_TOTAL: u64 = 72_916_666;
+const SELL_FEE: u64 = 3_645_833;
+
+#[tokio::test]
+async fn swap_sells_into_the_other_pool() {
+    let mut env = Env::start().await;
+    let mint_b = env.add_pool("other").await;
+    let pool_a = env.pool();
+    let pool_b = pool(&mint_b);
+
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&trader, LAMPORTS_PER_SOL / 2, BUY_AMOUNT).await.unwrap();
+
+    let pool_a_before = env.lamports(&pool_a).await;
+    let pool_b_before = env.lamports(&pool_b).await;
+    let treasury_before = env.lamports(&treasury()).await;
+    let vault_a_before = env.lamports(&creator_vault()).await;
+    let vault_b_before = env.lamports(&creator_vault_of("other")).await;
+    let trader_before = env.lamports(&trader.pubkey()).await;
+
+    assert!(env.swap(&trader, &mint_b, SWAP_AMOUNT, u64::MAX).await.is_err());
+    env.swap(&trader, &mint_b, SWAP_AMOUNT, 1).await.unwrap();
+
+    let minted = env.supply_of(&mint_b).await;
+    assert!(minted > 0);
+    assert_eq!(env.supply().await, BUY_AMOUNT - SWAP_AMOUNT);
+    assert_eq!(env.token_balance(&trader.pubkey()).await, BUY_AMOUNT - SWAP_AMOUNT);
+    assert_eq!(env.token_balance_of(&trader.pubkey(), &mint_b).await, minted);
+
+    // Fees are taken once, at pool A's rates, and both creators share the creator fee
+    assert_eq!(pool_a_before - env.lamports(&pool_a).await, SELL_TOTAL);
+    assert_eq!(env.lamports(&treasury()).await - treasury_before, SELL_FEE);
+    assert_eq!(env.lamports(&creator_vault()).await - vault_a_before, SELL_FEE - SELL_FEE / 2);
+    assert_eq!(env.lamports(&creator_vault_of("other")).await - vault_b_before, SELL_FEE / 2);
+
+    // What doesn't buy a whole base unit of B comes back, less the rent of the new accounts
+    let net = SELL_TOTAL - 2 * SELL_FEE;
+    let cost = env.lamports(&pool_b).await - pool_b_before;
+    assert!(cost <= net);
+    let accounts = env.lamports(&position(&pool_b, &trader.pubkey())).await
+        + env.lamports(&get_associated_token_address(&trader.pubkey(), &mint_b)).await;
+    assert_eq!(env.lamports(&trader.pubkey()).await + accounts - trader_before, net - cost);
+}
+
+#[tokio::test]
+async fn swap_has_to_buy_something() {
+    let mut env = Env::start().await;
+    let mint_b = env.add_pool("other").await;
+
+    let trader = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&trader, LAMPORTS_PER_SOL / 2, 1_000_000).await.unwrap();
+
+    // One base unit near the bottom of the curve is worth nothing in B
+    assert!(env.swap(&trader, &mint_b, 1, 0).await.is_err());
+    assert_eq!(env.supply().await, 1_000_000);
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::Rent;
+use banger_program_tests::*;
+use solana_sdk::{account::Account, native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+#[tokio::test]
+async fn tips_go_to_the_creator_vault() {
+    let mut env = Env::start().await;
+    let tipper = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&tipper, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+
+    let vault_before = env.lamports(&creator_vault()).await;
+    env.tip_creator(&tipper, LAMPORTS_PER_SOL / 10, None).await.unwrap();
+    assert_eq!(env.lamports(&creator_vault()).await - vault_before, LAMPORTS_PER_SOL / 10);
+
+    let mint = env.mint;
+    env.tip_creator(&tipper, 5_000_000, Some(mint)).await.unwrap();
+    assert_eq!(env.token_balance_of(&creator_vault(), &mint).await, 5_000_000);
+    assert_eq!(env.token_balance(&tipper.pubkey()).await, 15_000_000);
+
+    // The creator claims the SOL tips along with the fees
+    let creator = env.wallet(LAMPORTS_PER_SOL).await;
+    env.link_creator(&creator.pubkey()).await.unwrap();
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.claim(&creator).await.unwrap();
+    assert!(env.lamports(&creator.pubkey()).await - creator_before >= LAMPORTS_PER_SOL / 10);
+}
+
+#[tokio::test]
+async fn small_tip_funds_an_empty_vault() {
+    let mut env = Env::start().await;
+    // As for a creator id no pool has been launched for
+    env.ctx.set_account(&creator_vault(), &Account::default().into());
+
+    let tipper = env.wallet(LAMPORTS_PER_SOL).await;
+    env.tip_creator(&tipper, 1000, None).await.unwrap();
+    assert_eq!(env.lamports(&creator_vault()).await, Rent::default().minimum_balance(0) + 1000);
+}
@@ -0,0 +1,84 @@
+use anchor_lang::solana_program::{pubkey::Pubkey, rent::Rent};
+use anchor_spl::associated_token::get_associated_token_address;
+use banger_program::TreasuryShare;
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+#[tokio::test]
+async fn withdraw_treasury_keeps_rent_exemption() {
+    let mut env = Env::start().await;
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, 20_000_000).await.unwrap();
+
+    let rent = Rent::default().minimum_balance(0);
+    let available = env.lamports(&treasury()).await - rent;
+    assert!(available > 0);
+
+    assert!(env.withdraw_treasury(available + 1).await.is_err());
+
+    let admin = env.admin.pubkey();
+    let admin_before = env.lamports(&admin).await;
+    env.withdraw_treasury(available).await.unwrap();
+
+    assert_eq!(env.lamports(&treasury()).await, rent);
+    assert_eq!(env.lamports(&admin).await - admin_before, available);
+}
+
+#[tokio::test]
+async fn small_first_trade_pays_into_funded_treasury() {
+    let mut env = Env::start().await;
+    let rent = Rent::default().minimum_balance(0);
+    assert_eq!(env.lamports(&treasury()).await, rent);
+
+    // One whole token, its fee alone is far below the treasury's rent
+    let buyer = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&buyer, LAMPORTS_PER_SOL / 2, 1_000_000).await.unwrap();
+
+    let fee = env.lamports(&treasury()).await - rent;
+    assert!(fee > 0 && fee < rent);
+}
+
+#[tokio::test]
+async fn withdraw_treasury_tokens_to_admin() {
+    let mut env = Env::start().await;
+    let mint = env.spl_mint(&treasury(), 1000).await;
+
+    env.withdraw_treasury_tokens(&mint, 400, &[]).await.unwrap();
+    assert_eq!(env.token_balance_of(&env.admin.pubkey(), &mint).await, 400);
+    assert_eq!(env.token_balance_of(&treasury(), &mint).await, 600);
+}
+
+#[tokio::test]
+async fn withdraw_treasury_tokens_follows_split() {
+    let mut env = Env::start().await;
+    // Stands in for fees collected from a quote token pool
+    let mint = env.spl_mint(&treasury(), 1000).await;
+
+    let recipients = [Pubkey::new_unique(), Pubkey::new_unique()];
+    for recipient in &recipients {
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &env.ctx.payer.pubkey(),
+            recipient,
+            &mint,
+            &spl_token::ID
+        );
+        send(&mut env.ctx, &[ix], &[]).await.unwrap();
+    }
+    env.set_treasury_split(vec![
+        TreasuryShare { recipient: recipients[0], share: 3000 },
+        TreasuryShare { recipient: recipients[1], share: 7000 }
+    ]).await.unwrap();
+
+    // The admin alone no longer gets it
+    assert!(env.withdraw_treasury_tokens(&mint, 1000, &[]).await.is_err());
+    assert!(env.withdraw_treasury_tokens(&mint, 1000, &[recipients[1], recipients[0]]).await.is_err());
+
+    env.withdraw_treasury_tokens(&mint, 1000, &recipients).await.unwrap();
+    assert_eq!(env.token_balance_of(&recipients[0], &mint).await, 300);
+    assert_eq!(env.token_balance_of(&recipients[1], &mint).await, 700);
+    assert_eq!(env.token_balance_of(&treasury(), &mint).await, 0);
+
+    // Nor does the admin pay for an ATA it gets nothing in
+    let admin_ata = get_associated_token_address(&env.admin.pubkey(), &mint);
+    assert!(env.ctx.banks_client.get_account(admin_ata).await.unwrap().is_none());
+}
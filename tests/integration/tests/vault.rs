@@ -0,0 +1,41 @@
+use anchor_lang::solana_program::rent::Rent;
+use banger_program::ReclaimTarget;
+use banger_program_tests::*;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+const AMOUNT: u64 = 20_000_000;
+
+#[tokio::test]
+async fn reclaim_to_stakers_needs_the_pool() {
+    let mut env = Env::start().await;
+    let staker = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&staker, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+    env.stake(&staker, AMOUNT).await.unwrap();
+
+    env.set_vault_expiry(1, ReclaimTarget::Stakers).await.unwrap();
+    env.warp(10).await;
+
+    assert!(env.reclaim_vault(false, None).await.is_err());
+
+    // The vault keeps its rent exemption
+    let rent = Rent::default().minimum_balance(0);
+    let vault = env.lamports(&creator_vault()).await;
+    let pool = env.pool();
+    let before = env.lamports(&pool).await;
+    env.reclaim_vault(true, None).await.unwrap();
+    assert_eq!(env.lamports(&creator_vault()).await, rent);
+    assert_eq!(env.lamports(&pool).await, before + vault - rent);
+}
+
+#[tokio::test]
+async fn reclaim_sweeps_vault_tokens() {
+    let mut env = Env::start().await;
+    let mint = env.spl_mint(&creator_vault(), 1000).await;
+
+    env.set_vault_expiry(1, ReclaimTarget::Treasury).await.unwrap();
+    env.warp(10).await;
+
+    env.reclaim_vault(false, Some(mint)).await.unwrap();
+    assert_eq!(env.token_balance_of(&creator_vault(), &mint).await, 0);
+    assert_eq!(env.token_balance_of(&treasury(), &mint).await, 1000);
+}
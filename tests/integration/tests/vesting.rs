@@ -0,0 +1,36 @@
+use banger_program_tests::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+
+const AMOUNT: u64 = 20_000_000;
+const CLIFF: i64 = 100;
+const DURATION: i64 = 1000;
+
+#[tokio::test]
+async fn vesting_releases_linearly_and_revokes_the_rest() {
+    let mut env = Env::start().await;
+    let funder = env.wallet(LAMPORTS_PER_SOL).await;
+    let beneficiary = env.wallet(LAMPORTS_PER_SOL).await;
+    env.buy(&funder, LAMPORTS_PER_SOL / 2, AMOUNT).await.unwrap();
+
+    let start = env.now().await;
+    env.create_vesting(&funder, &beneficiary.pubkey(), AMOUNT, start, start + CLIFF, DURATION).await.unwrap();
+    assert_eq!(env.token_balance(&funder.pubkey()).await, 0);
+
+    // Nothing before the cliff
+    env.claim_vested(&beneficiary, &funder.pubkey()).await.unwrap();
+    assert_eq!(env.token_balance(&beneficiary.pubkey()).await, 0);
+
+    env.warp(DURATION / 2).await;
+    env.claim_vested(&beneficiary, &funder.pubkey()).await.unwrap();
+    let claimed = env.token_balance(&beneficiary.pubkey()).await;
+    assert!((AMOUNT / 2..AMOUNT).contains(&claimed));
+
+    // Only the admin revokes, and the funder gets back what hadn't vested
+    env.revoke_unvested(&beneficiary.pubkey(), &funder.pubkey()).await.unwrap();
+    let returned = env.token_balance(&funder.pubkey()).await;
+    assert!(returned > 0);
+
+    env.warp(DURATION).await;
+    env.claim_vested(&beneficiary, &funder.pubkey()).await.unwrap();
+    assert_eq!(env.token_balance(&beneficiary.pubkey()).await + returned, AMOUNT);
+}